@@ -2,7 +2,11 @@
 #![allow(missing_docs)]
 
 use metadata_gen::{
-    metadata::{extract_metadata, process_metadata, Metadata},
+    metadata::{
+        extract_metadata, process_metadata,
+        process_metadata_with_options, DateParser, Metadata,
+        ProcessOptions,
+    },
     MetadataError,
 };
 use std::collections::HashMap;
@@ -15,6 +19,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     json_metadata_example()?;
     complex_metadata_example()?;
     metadata_processing_example()?;
+    custom_date_parser_example()?;
 
     println!("\n🎉 All metadata examples completed successfully!");
 
@@ -166,9 +171,54 @@ fn metadata_processing_example() -> Result<(), MetadataError> {
     Ok(())
 }
 
+/// Parses quarter dates such as `2023-Q2`, using the first day of the quarter.
+struct QuarterDateParser;
+
+impl DateParser for QuarterDateParser {
+    fn parse_to_ymd(
+        &self,
+        input: &str,
+    ) -> Result<(i32, u8, u8), MetadataError> {
+        let invalid =
+            || MetadataError::DateParseError(input.to_string());
+        let (year, quarter) =
+            input.split_once("-Q").ok_or_else(invalid)?;
+        let year = year.parse().map_err(|_| invalid())?;
+        match quarter.parse::<u8>() {
+            Ok(quarter @ 1..=4) => Ok((year, (quarter - 1) * 3 + 1, 1)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+fn custom_date_parser_example() -> Result<(), MetadataError> {
+    println!("\n🦀 Custom Date Parser Example");
+    println!("---------------------------------------------");
+
+    let mut raw_metadata = HashMap::new();
+    raw_metadata
+        .insert("title".to_string(), "Quarterly Report".to_string());
+    raw_metadata.insert("date".to_string(), "2023-Q2".to_string());
+    let metadata = Metadata::new(raw_metadata);
+
+    let options = ProcessOptions {
+        date_parser: &QuarterDateParser,
+        ..ProcessOptions::default()
+    };
+    match process_metadata_with_options(&metadata, &options) {
+        Ok(processed) => {
+            println!("    ✅ Successfully processed metadata");
+            print_metadata(&processed);
+        }
+        Err(e) => println!("    ❌ Failed to process metadata: {}", e),
+    }
+
+    Ok(())
+}
+
 fn print_metadata(metadata: &Metadata) {
     println!("    📊 Extracted Metadata:");
-    for (key, value) in metadata.clone().into_inner().iter() {
+    for (key, value) in metadata.as_map().iter() {
         println!("       {}: {}", key, value);
     }
 }
@@ -4,7 +4,7 @@
 use metadata_gen::{
     metatags::{
         extract_meta_tags, generate_metatags, meta_tags_to_hashmap,
-        MetaTag, MetaTagGroups,
+        MetaTag, MetaTagAttribute, MetaTagGroups,
     },
     MetadataError,
 };
@@ -127,14 +127,17 @@ fn meta_tags_to_hashmap_example() -> Result<(), MetadataError> {
         MetaTag {
             name: "description".to_string(),
             content: "A sample page".to_string(),
+            attribute: MetaTagAttribute::Name,
         },
         MetaTag {
             name: "og:title".to_string(),
             content: "Sample Title".to_string(),
+            attribute: MetaTagAttribute::Property,
         },
         MetaTag {
             name: "keywords".to_string(),
             content: "sample, meta tags, rust".to_string(),
+            attribute: MetaTagAttribute::Name,
         },
     ];
 
@@ -31,6 +31,23 @@ fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
     }
 }
 
+/// Broad categories of `MetadataError`s, for grouping errors in logs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// Front matter could not be located or is in an unsupported format.
+    Extraction,
+    /// Extracted metadata could not be processed.
+    Processing,
+    /// Metadata fields are missing or invalid.
+    Validation,
+    /// An I/O operation failed.
+    Io,
+    /// Input could not be parsed or decoded.
+    Parse,
+    /// Any other error.
+    Other,
+}
+
 /// Custom error types for the metadata-gen library.
 ///
 /// This enum encompasses all possible errors that can occur during
@@ -175,6 +192,123 @@ pub fn new_validation_error(
         }
     }
 
+    /// Returns the broad category of the error.
+    ///
+    /// # Returns
+    ///
+    /// The `ErrorCategory` of the error variant.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metadata_gen::error::{ErrorCategory, MetadataError};
+    ///
+    /// let error = MetadataError::MissingFieldError("title".to_string());
+    /// assert_eq!(error.category(), ErrorCategory::Validation);
+    /// ```
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::ExtractionError { .. }
+            | Self::UnsupportedFormatError(_) => {
+                ErrorCategory::Extraction
+            }
+            Self::ProcessingError { .. } => ErrorCategory::Processing,
+            Self::MissingFieldError(_)
+            | Self::ValidationError { .. } => ErrorCategory::Validation,
+            Self::IoError(_) => ErrorCategory::Io,
+            Self::DateParseError(_)
+            | Self::YamlError(_)
+            | Self::JsonError(_)
+            | Self::TomlError(_)
+            | Self::Utf8Error(_) => ErrorCategory::Parse,
+            Self::Other(_) => ErrorCategory::Other,
+        }
+    }
+
+    /// Returns the underlying I/O error, if this is an `IoError`.
+    ///
+    /// # Returns
+    ///
+    /// `Some(&std::io::Error)` for the `IoError` variant, `None` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metadata_gen::error::MetadataError;
+    /// use std::io;
+    ///
+    /// let error: MetadataError =
+    ///     io::Error::new(io::ErrorKind::NotFound, "missing").into();
+    /// assert_eq!(
+    ///     error.as_io_error().map(|e| e.kind()),
+    ///     Some(io::ErrorKind::NotFound)
+    /// );
+    /// ```
+    pub fn as_io_error(&self) -> Option<&std::io::Error> {
+        match self {
+            Self::IoError(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Converts this error into the underlying I/O error.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(std::io::Error)` for the `IoError` variant, or `Err(self)` for
+    /// any other variant.
+    pub fn into_io_error(self) -> Result<std::io::Error, Self> {
+        match self {
+            Self::IoError(error) => Ok(error),
+            other => Err(other),
+        }
+    }
+
+    /// Returns the underlying YAML error, if this is a `YamlError`.
+    ///
+    /// # Returns
+    ///
+    /// `Some(&serde_yml::Error)` for the `YamlError` variant, `None` otherwise.
+    pub fn as_yaml_error(&self) -> Option<&SerdeYmlError> {
+        match self {
+            Self::YamlError(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Returns the underlying JSON error, if this is a `JsonError`.
+    ///
+    /// # Returns
+    ///
+    /// `Some(&serde_json::Error)` for the `JsonError` variant, `None` otherwise.
+    pub fn as_json_error(&self) -> Option<&serde_json::Error> {
+        match self {
+            Self::JsonError(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Returns the underlying TOML error, if this is a `TomlError`.
+    ///
+    /// # Returns
+    ///
+    /// `Some(&toml::de::Error)` for the `TomlError` variant, `None` otherwise.
+    pub fn as_toml_error(&self) -> Option<&toml::de::Error> {
+        match self {
+            Self::TomlError(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Checks whether this error reports a missing required field.
+    ///
+    /// # Returns
+    ///
+    /// `true` for the `MissingFieldError` variant, `false` otherwise.
+    pub fn is_missing_field(&self) -> bool {
+        matches!(self, Self::MissingFieldError(_))
+    }
+
     /// Adds context to an existing error.
     ///
     /// This method wraps the current error with additional context information.
@@ -245,7 +379,14 @@ pub fn context<C>(self, ctx: C) -> Self
                     message: format!("{}: {}", ctx, message),
                 }
             }
-            Self::Utf8Error(error) => Self::Utf8Error(error),
+            // `Utf8Error` cannot carry a message, so the context is kept by
+            // wrapping it in a `ContextError` instead.
+            Self::Utf8Error(error) => {
+                Self::Other(Box::new(ContextError {
+                    context: ctx.to_string(),
+                    source: Box::new(error),
+                }))
+            }
             Self::Other(error) => Self::Other(Box::new(ContextError {
                 context: ctx.to_string(),
                 source: error,
@@ -402,6 +543,140 @@ impl Error for CustomError {}
         );
     }
 
+    #[test]
+    fn test_as_io_error() {
+        let error: MetadataError =
+            io::Error::new(io::ErrorKind::NotFound, "File not found")
+                .into();
+        assert_eq!(
+            error.as_io_error().map(|e| e.kind()),
+            Some(io::ErrorKind::NotFound)
+        );
+        assert!(error.as_yaml_error().is_none());
+        assert!(error.as_json_error().is_none());
+        assert!(error.as_toml_error().is_none());
+        assert!(!error.is_missing_field());
+        assert_eq!(
+            error.into_io_error().unwrap().to_string(),
+            "File not found"
+        );
+
+        let error = MetadataError::new_extraction_error("No I/O");
+        assert!(error.as_io_error().is_none());
+        assert!(matches!(
+            error.into_io_error(),
+            Err(MetadataError::ExtractionError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_as_serde_errors() {
+        let error: MetadataError =
+            serde_yml::Error::custom("bad yaml").into();
+        assert!(error.as_yaml_error().is_some());
+        assert!(error.as_json_error().is_none());
+        assert!(error.as_toml_error().is_none());
+        assert!(error.as_io_error().is_none());
+
+        let error: MetadataError =
+            serde_json::Error::custom("bad json").into();
+        assert_eq!(
+            error.as_json_error().map(|e| e.to_string()),
+            Some("bad json".to_string())
+        );
+        assert!(error.as_yaml_error().is_none());
+
+        let error: MetadataError =
+            toml::de::Error::custom("bad toml").into();
+        assert!(error.as_toml_error().is_some());
+        assert!(error.as_json_error().is_none());
+    }
+
+    #[test]
+    #[allow(invalid_from_utf8)]
+    fn test_category() {
+        let utf8_error =
+            std::str::from_utf8(&[0xFF, 0xFF]).unwrap_err();
+        let cases: Vec<(MetadataError, ErrorCategory)> = vec![
+            (
+                MetadataError::new_extraction_error("extract"),
+                ErrorCategory::Extraction,
+            ),
+            (
+                MetadataError::UnsupportedFormatError(
+                    "XML".to_string(),
+                ),
+                ErrorCategory::Extraction,
+            ),
+            (
+                MetadataError::new_processing_error("process"),
+                ErrorCategory::Processing,
+            ),
+            (
+                MetadataError::MissingFieldError("title".to_string()),
+                ErrorCategory::Validation,
+            ),
+            (
+                MetadataError::new_validation_error("title", "empty"),
+                ErrorCategory::Validation,
+            ),
+            (
+                io::Error::new(io::ErrorKind::Other, "io").into(),
+                ErrorCategory::Io,
+            ),
+            (
+                MetadataError::DateParseError("date".to_string()),
+                ErrorCategory::Parse,
+            ),
+            (
+                serde_yml::Error::custom("yaml").into(),
+                ErrorCategory::Parse,
+            ),
+            (
+                serde_json::Error::custom("json").into(),
+                ErrorCategory::Parse,
+            ),
+            (
+                toml::de::Error::custom("toml").into(),
+                ErrorCategory::Parse,
+            ),
+            (utf8_error.into(), ErrorCategory::Parse),
+            (
+                MetadataError::Other("other".into()),
+                ErrorCategory::Other,
+            ),
+        ];
+
+        for (error, category) in cases {
+            assert_eq!(error.category(), category, "{}", error);
+        }
+    }
+
+    #[test]
+    #[allow(invalid_from_utf8)]
+    fn test_utf8_error_context() {
+        let utf8_error =
+            std::str::from_utf8(&[0xFF, 0xFF]).unwrap_err();
+        let error =
+            MetadataError::from(utf8_error).context("reading file");
+
+        assert!(error.to_string().contains("reading file: "));
+        assert!(error.to_string().contains(&utf8_error.to_string()));
+        assert!(error
+            .source()
+            .and_then(|e| e.source())
+            .and_then(|e| e.downcast_ref::<std::str::Utf8Error>())
+            .is_some());
+    }
+
+    #[test]
+    fn test_is_missing_field() {
+        assert!(MetadataError::MissingFieldError("title".to_string())
+            .is_missing_field());
+        assert!(!MetadataError::new_validation_error("title", "empty")
+            .is_missing_field());
+    }
+
     #[test]
     fn test_extraction_error_with_empty_message() {
         let error = MetadataError::new_extraction_error("");
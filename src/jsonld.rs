@@ -0,0 +1,418 @@
+//! JSON-LD structured data generation module.
+//!
+//! This module provides functionality for generating schema.org structured
+//! data from metadata and wrapping it in `<script type="application/ld+json">`
+//! elements, and for extracting existing structured data from HTML.
+
+use crate::error::MetadataError;
+use scraper::{Html, Selector};
+use serde_json::{json, Map, Value as JsonValue};
+use std::collections::HashMap;
+
+/// Generates a schema.org JSON-LD object describing the page.
+///
+/// The `@type` is `Article` when `og:type` is `article`, and `WebPage`
+/// otherwise. The following properties are filled when the corresponding
+/// keys are present:
+/// - `headline` from `title`
+/// - `description` from `description`
+/// - `datePublished` from `date`
+/// - `author` from `author`, as a `Person`
+/// - `url` from `canonical`, falling back to `og:url`
+/// - `image` from `og:image`
+///
+/// # Arguments
+///
+/// * `metadata` - A reference to a HashMap containing the metadata.
+///
+/// # Returns
+///
+/// The JSON-LD object, or `None` if none of the properties are available.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::jsonld::generate_json_ld;
+/// use std::collections::HashMap;
+///
+/// let mut metadata = HashMap::new();
+/// metadata.insert("title".to_string(), "My Page".to_string());
+///
+/// let json_ld = generate_json_ld(&metadata).unwrap();
+/// assert_eq!(json_ld["@type"], "WebPage");
+/// assert_eq!(json_ld["headline"], "My Page");
+/// ```
+pub fn generate_json_ld(
+    metadata: &HashMap<String, String>,
+) -> Option<JsonValue> {
+    let mut properties = Map::new();
+
+    let fields = [
+        ("headline", "title"),
+        ("description", "description"),
+        ("datePublished", "date"),
+        ("image", "og:image"),
+    ];
+    for (property, key) in fields {
+        if let Some(value) = metadata.get(key) {
+            properties.insert(property.to_string(), json!(value));
+        }
+    }
+    if let Some(author) = metadata.get("author") {
+        properties.insert(
+            "author".to_string(),
+            json!({ "@type": "Person", "name": author }),
+        );
+    }
+    if let Some(url) =
+        metadata.get("canonical").or_else(|| metadata.get("og:url"))
+    {
+        properties.insert("url".to_string(), json!(url));
+    }
+
+    if properties.is_empty() {
+        return None;
+    }
+
+    let schema_type = match metadata.get("og:type") {
+        Some(og_type) if og_type.trim() == "article" => "Article",
+        _ => "WebPage",
+    };
+    properties
+        .insert("@context".to_string(), json!("https://schema.org"));
+    properties.insert("@type".to_string(), json!(schema_type));
+
+    Some(JsonValue::Object(properties))
+}
+
+/// Wraps a JSON-LD value in a `<script type="application/ld+json">` element.
+///
+/// Any `</` sequence in the serialized JSON is escaped as `<\/` so that
+/// string values cannot close the script element early.
+///
+/// # Arguments
+///
+/// * `value` - The JSON-LD value to wrap.
+///
+/// # Returns
+///
+/// A `Result` containing the script element, or a `MetadataError` if the
+/// value cannot be serialized.
+///
+/// # Errors
+///
+/// Returns a `MetadataError::JsonError` if serialization fails.
+pub fn json_ld_script(
+    value: &JsonValue,
+) -> Result<String, MetadataError> {
+    let json = serde_json::to_string(value)?;
+    Ok(wrap_json_ld(&json))
+}
+
+/// Generates a schema.org `BreadcrumbList` JSON-LD script.
+///
+/// Each crumb becomes a `ListItem` whose `position` starts at 1 and
+/// follows the order of `crumbs`. The crumbs are checked with
+/// `validate_breadcrumbs` first.
+///
+/// # Arguments
+///
+/// * `crumbs` - The `(name, url)` pairs of the breadcrumb trail, from the
+///   root to the current page.
+///
+/// # Returns
+///
+/// A `Result` containing a `<script type="application/ld+json">` element
+/// with the list.
+///
+/// # Errors
+///
+/// Returns a `MetadataError::ValidationError` if a crumb has an empty name
+/// or URL, or a `MetadataError::JsonError` if the list cannot be
+/// serialized.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::jsonld::generate_breadcrumbs_json_ld;
+///
+/// let script = generate_breadcrumbs_json_ld(&[
+///     ("Home".to_string(), "https://example.com/".to_string()),
+///     ("Blog".to_string(), "https://example.com/blog/".to_string()),
+/// ])
+/// .unwrap();
+/// assert!(script.contains(r#""position":2"#));
+///
+/// let missing_url = [("Home".to_string(), String::new())];
+/// assert!(generate_breadcrumbs_json_ld(&missing_url).is_err());
+/// ```
+pub fn generate_breadcrumbs_json_ld(
+    crumbs: &[(String, String)],
+) -> Result<String, MetadataError> {
+    validate_breadcrumbs(crumbs)?;
+
+    let items = crumbs
+        .iter()
+        .enumerate()
+        .map(|(index, (name, url))| {
+            json!({
+                "@type": "ListItem",
+                "position": index + 1,
+                "name": name,
+                "item": url,
+            })
+        })
+        .collect::<Vec<_>>();
+    let breadcrumbs = json!({
+        "@context": "https://schema.org",
+        "@type": "BreadcrumbList",
+        "itemListElement": items,
+    });
+
+    json_ld_script(&breadcrumbs)
+}
+
+/// Checks that every breadcrumb has a name and a URL.
+///
+/// # Arguments
+///
+/// * `crumbs` - The `(name, url)` pairs of the breadcrumb trail.
+///
+/// # Returns
+///
+/// `Ok(())` if every crumb is complete.
+///
+/// # Errors
+///
+/// Returns a `MetadataError::ValidationError` naming the first crumb, by
+/// its 1-based position, whose name or URL is empty.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::jsonld::validate_breadcrumbs;
+///
+/// let crumbs = [("Home".to_string(), String::new())];
+/// assert!(validate_breadcrumbs(&crumbs).is_err());
+/// ```
+pub fn validate_breadcrumbs(
+    crumbs: &[(String, String)],
+) -> Result<(), MetadataError> {
+    for (index, (name, url)) in crumbs.iter().enumerate() {
+        let field = format!("breadcrumbs.{}", index + 1);
+        if name.trim().is_empty() {
+            return Err(MetadataError::new_validation_error(
+                field,
+                "Breadcrumb name must not be empty",
+            ));
+        }
+        if url.trim().is_empty() {
+            return Err(MetadataError::new_validation_error(
+                field,
+                "Breadcrumb URL must not be empty",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Extracts the JSON-LD structured data of an HTML document.
+///
+/// Every `<script type="application/ld+json">` element is parsed as JSON,
+/// in document order.
+///
+/// # Arguments
+///
+/// * `html` - A string slice containing the HTML content to parse.
+///
+/// # Returns
+///
+/// A `Result` containing the parsed JSON values.
+///
+/// # Errors
+///
+/// Returns a `MetadataError::JsonError` if a block is not valid JSON. The
+/// message names the failing block by its 1-based position.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::jsonld::extract_json_ld;
+///
+/// let html = r#"<script type="application/ld+json">{"@type": "WebPage"}</script>"#;
+/// let values = extract_json_ld(html).unwrap();
+/// assert_eq!(values[0]["@type"], "WebPage");
+/// ```
+pub fn extract_json_ld(
+    html: &str,
+) -> Result<Vec<JsonValue>, MetadataError> {
+    let document = Html::parse_document(html);
+    let selector =
+        Selector::parse(r#"script[type="application/ld+json"]"#)
+            .map_err(|e| MetadataError::ExtractionError {
+                message: format!(
+                    "Failed to create JSON-LD selector: {}",
+                    e
+                ),
+            })?;
+
+    document
+        .select(&selector)
+        .enumerate()
+        .map(|(index, script)| {
+            let json = script.text().collect::<String>();
+            serde_json::from_str(&json).map_err(|e| {
+                MetadataError::from(e)
+                    .context(format!("JSON-LD block {}", index + 1))
+            })
+        })
+        .collect()
+}
+
+/// Wraps serialized JSON in a `<script type="application/ld+json">` element.
+fn wrap_json_ld(json: &str) -> String {
+    format!(
+        r#"<script type="application/ld+json">{}</script>"#,
+        json.replace("</", r"<\/")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_json_ld_article() {
+        let mut metadata = HashMap::new();
+        metadata.insert("title".to_string(), "My Post".to_string());
+        metadata.insert("og:type".to_string(), "article".to_string());
+        metadata.insert("date".to_string(), "2023-05-20".to_string());
+        metadata.insert("author".to_string(), "Jane Doe".to_string());
+        metadata.insert(
+            "og:url".to_string(),
+            "https://example.com/post".to_string(),
+        );
+
+        let json_ld = generate_json_ld(&metadata).unwrap();
+        assert_eq!(json_ld["@context"], "https://schema.org");
+        assert_eq!(json_ld["@type"], "Article");
+        assert_eq!(json_ld["headline"], "My Post");
+        assert_eq!(json_ld["datePublished"], "2023-05-20");
+        assert_eq!(json_ld["author"]["name"], "Jane Doe");
+        assert_eq!(json_ld["url"], "https://example.com/post");
+        assert!(json_ld.get("description").is_none());
+    }
+
+    #[test]
+    fn test_generate_json_ld_empty() {
+        let mut metadata = HashMap::new();
+        metadata.insert("layout".to_string(), "post".to_string());
+        assert!(generate_json_ld(&metadata).is_none());
+    }
+
+    #[test]
+    fn test_generate_breadcrumbs_json_ld() {
+        let crumbs = vec![
+            ("Home".to_string(), "https://example.com/".to_string()),
+            (
+                "Blog".to_string(),
+                "https://example.com/blog/".to_string(),
+            ),
+            (
+                "Say \"Hi\" </script>".to_string(),
+                "https://example.com/blog/hi/".to_string(),
+            ),
+        ];
+
+        let script = generate_breadcrumbs_json_ld(&crumbs).unwrap();
+        let json = script
+            .strip_prefix(r#"<script type="application/ld+json">"#)
+            .and_then(|s| s.strip_suffix("</script>"))
+            .unwrap();
+        assert!(!json.contains("</"));
+
+        let value: JsonValue = serde_json::from_str(json).unwrap();
+        assert_eq!(value["@type"], "BreadcrumbList");
+        let items = value["itemListElement"].as_array().unwrap();
+        assert_eq!(items.len(), 3);
+        for (index, (item, (name, url))) in
+            items.iter().zip(&crumbs).enumerate()
+        {
+            assert_eq!(item["@type"], "ListItem");
+            assert_eq!(item["position"], index + 1);
+            assert_eq!(item["name"], name.as_str());
+            assert_eq!(item["item"], url.as_str());
+        }
+    }
+
+    #[test]
+    fn test_validate_breadcrumbs() {
+        let mut crumbs = vec![
+            ("Home".to_string(), "https://example.com/".to_string()),
+            (
+                "Docs".to_string(),
+                "https://example.com/docs/".to_string(),
+            ),
+        ];
+        assert!(validate_breadcrumbs(&crumbs).is_ok());
+
+        crumbs.push(("Page".to_string(), " ".to_string()));
+        match validate_breadcrumbs(&crumbs) {
+            Err(MetadataError::ValidationError { field, message }) => {
+                assert_eq!(field, "breadcrumbs.3");
+                assert!(message.contains("URL"));
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        assert!(matches!(
+            generate_breadcrumbs_json_ld(&crumbs),
+            Err(MetadataError::ValidationError { field, .. })
+                if field == "breadcrumbs.3"
+        ));
+    }
+
+    #[test]
+    fn test_extract_json_ld() {
+        let html = r#"<html><head>
+<script type="application/ld+json">{"@context": "https://schema.org", "@type": "Article", "headline": "<\/script>"}</script>
+<script type="text/javascript">var x = 1;</script>
+<script type="application/ld+json">
+  [{"@type": "Person", "name": "Jane"}]
+</script>
+</head></html>"#;
+
+        let values = extract_json_ld(html).unwrap();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[0]["@type"], "Article");
+        assert_eq!(values[0]["headline"], "</script>");
+        assert_eq!(values[1][0]["name"], "Jane");
+
+        let generated = json_ld_script(&values[0]).unwrap();
+        assert_eq!(extract_json_ld(&generated).unwrap()[0], values[0]);
+
+        assert!(extract_json_ld("<p>None</p>").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_extract_json_ld_malformed_block() {
+        let html = r#"<head>
+<script type="application/ld+json">{"@type": "WebPage"}</script>
+<script type="application/ld+json">{"@type": "Article",}</script>
+</head>"#;
+
+        let error = extract_json_ld(html).unwrap_err();
+        assert!(matches!(error, MetadataError::JsonError(_)));
+        assert!(error.to_string().contains("JSON-LD block 2"));
+    }
+
+    #[test]
+    fn test_json_ld_script_escapes_closing_tags() {
+        let value = json!({ "headline": "</script><b>" });
+        let script = json_ld_script(&value).unwrap();
+        assert_eq!(
+            script,
+            r#"<script type="application/ld+json">{"headline":"<\/script><b>"}</script>"#
+        );
+    }
+}
@@ -9,14 +9,18 @@
 #![crate_name = "metadata_gen"]
 #![crate_type = "lib"]
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// The `error` module contains error types for metadata processing.
 pub mod error;
+/// The `jsonld` module contains functions for generating JSON-LD structured data.
+pub mod jsonld;
 /// The `metadata` module contains functions for extracting and processing metadata.
 pub mod metadata;
 /// The `metatags` module contains functions for generating meta tags.
 pub mod metatags;
+/// The `seo` module contains functions for linting metadata for SEO issues.
+pub mod seo;
 /// The `utils` module contains utility functions for metadata processing.
 pub mod utils;
 
@@ -41,6 +45,10 @@
 /// 2. It generates keywords based on this metadata.
 /// 3. It generates various meta tags required for the page.
 ///
+/// The metadata is returned as extracted, without standardizing the date
+/// or generating derived fields such as `slug`. Use
+/// `extract_and_process_metadata` to run `process_metadata` as well.
+///
 /// # Arguments
 ///
 /// * `content` - A string slice representing the content from which to extract metadata.
@@ -54,7 +62,7 @@
 ///
 /// # Errors
 ///
-/// This function will return a `MetadataError` if metadata extraction or processing fails.
+/// This function will return a `MetadataError` if metadata extraction fails.
 ///
 /// # Example
 ///
@@ -80,16 +88,257 @@ pub fn extract_and_prepare_metadata(content: &str) -> MetadataResult {
     }
 
     let metadata = extract_metadata(content)?;
-    let metadata_map = metadata.into_inner();
+    let mut metadata_map = metadata.into_inner();
+    normalize_list_fields(&mut metadata_map);
+    let keywords = extract_keywords(&metadata_map);
+    let all_meta_tags = generate_metatags(&metadata_map);
+
+    Ok((metadata_map, keywords, all_meta_tags))
+}
+
+/// The fields whose list values are normalized to comma-separated strings.
+const LIST_FIELDS: [&str; 2] = ["keywords", "tags"];
+
+/// Normalizes list-valued `keywords` and `tags` fields.
+///
+/// Lists in YAML or TOML front matter are flattened to inline lists such
+/// as `[a, b, c]`, which are converted to `a, b, c` so that the keywords
+/// and meta tags do not contain the brackets.
+fn normalize_list_fields(metadata: &mut HashMap<String, String>) {
+    for field in LIST_FIELDS {
+        if let Some(value) = metadata.get_mut(field) {
+            *value = metadata::inline_list_to_comma_separated(value);
+        }
+    }
+}
+
+/// Extracts and prepares metadata, collecting non-fatal warnings.
+///
+/// This behaves like `extract_and_prepare_metadata`, but uses
+/// `metadata::extract_metadata_with_warnings`, so that problems such as
+/// duplicate keys in the front matter are reported as warnings instead of
+/// failing the extraction. Each warning names the offending field.
+///
+/// # Arguments
+///
+/// * `content` - A string slice representing the content from which to extract metadata.
+///
+/// # Returns
+///
+/// A tuple of the `MetadataResult` and the warnings.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::extract_and_prepare_metadata_verbose;
+///
+/// let content = "---\ntitle: Draft\ndraft: true\n---\nBody";
+/// let (result, warnings) = extract_and_prepare_metadata_verbose(content);
+/// assert!(result.is_ok());
+/// assert_eq!(warnings, vec!["draft: boolean value coerced to string"]);
+/// ```
+pub fn extract_and_prepare_metadata_verbose(
+    content: &str,
+) -> (MetadataResult, Vec<String>) {
+    let (metadata, warnings) =
+        metadata::extract_metadata_with_warnings(content);
+    let result = metadata.map(|metadata| {
+        let mut metadata_map = metadata.into_inner();
+        normalize_list_fields(&mut metadata_map);
+        let keywords = extract_keywords(&metadata_map);
+        let all_meta_tags = generate_metatags(&metadata_map);
+        (metadata_map, keywords, all_meta_tags)
+    });
+
+    (result, warnings)
+}
+
+/// Extracts and processes metadata from the content, then generates
+/// keywords and meta tag groups.
+///
+/// This behaves like `extract_and_prepare_metadata`, but runs
+/// `process_metadata` on the extracted metadata first, so the returned map
+/// has a standardized `date` and a generated `slug`. A missing
+/// `description` is generated from the body with
+/// `metadata::generate_description`. The keywords and meta tags are
+/// generated from the processed metadata.
+///
+/// With the `whatlang` feature, a missing `lang` field is filled in with
+/// the language of the body, without the front matter, as detected by
+/// `utils::detect_language`, when it can be detected.
+///
+/// # Arguments
+///
+/// * `content` - A string slice representing the content from which to extract metadata.
+///
+/// # Returns
+///
+/// Returns a Result containing a tuple of the processed metadata, the
+/// keywords, and the meta tag groups.
+///
+/// # Errors
+///
+/// This function will return a `MetadataError` if metadata extraction or
+/// processing fails, including when the required `title` or `date` fields
+/// are missing.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::extract_and_process_metadata;
+///
+/// let content = "---\ntitle: My Page\ndate: 2023-05-20T10:00:00Z\n---\n";
+/// let (metadata, _, _) = extract_and_process_metadata(content).unwrap();
+/// assert_eq!(metadata.get("date").unwrap(), "2023-05-20");
+/// assert_eq!(metadata.get("slug").unwrap(), "my-page");
+/// ```
+pub fn extract_and_process_metadata(content: &str) -> MetadataResult {
+    let metadata = extract_metadata(content)?;
+    let mut metadata_map = process_metadata(&metadata)?.into_inner();
+    normalize_list_fields(&mut metadata_map);
+
+    if !metadata_map.contains_key("description") {
+        let description = metadata::generate_description(
+            content,
+            metadata::DEFAULT_DESCRIPTION_LENGTH,
+        );
+        if !description.is_empty() {
+            metadata_map.insert("description".to_string(), description);
+        }
+    }
+
+    #[cfg(feature = "whatlang")]
+    if !metadata_map.contains_key("lang") {
+        let body = metadata::strip_front_matter(content);
+        if let Some(lang) = utils::detect_language(body) {
+            metadata_map.insert("lang".to_string(), lang);
+        }
+    }
+
     let keywords = extract_keywords(&metadata_map);
     let all_meta_tags = generate_metatags(&metadata_map);
 
     Ok((metadata_map, keywords, all_meta_tags))
 }
 
+/// Extracts and prepares metadata from many documents in parallel.
+///
+/// Each document is processed as by `extract_and_prepare_metadata`, using
+/// the rayon thread pool. This is only available with the `rayon` feature.
+///
+/// # Arguments
+///
+/// * `contents` - The documents from which to extract metadata.
+///
+/// # Returns
+///
+/// A vector with one `MetadataResult` per document, in the order of
+/// `contents`.
+#[cfg(feature = "rayon")]
+pub fn extract_metadata_batch(
+    contents: &[&str],
+) -> Vec<MetadataResult> {
+    use rayon::prelude::*;
+
+    contents
+        .par_iter()
+        .map(|content| extract_and_prepare_metadata(content))
+        .collect()
+}
+
+/// Extracts and prepares metadata from many documents in parallel,
+/// skipping drafts.
+///
+/// This behaves like `extract_metadata_batch`, but documents whose
+/// metadata is marked as a draft, as determined by `metadata::is_draft`,
+/// are left out. Documents that fail to parse are kept so that their
+/// errors can still be reported. This is only available with the `rayon`
+/// feature.
+///
+/// # Arguments
+///
+/// * `contents` - The documents from which to extract metadata.
+///
+/// # Returns
+///
+/// A vector of `(index, MetadataResult)` pairs for the documents that are
+/// not drafts, where `index` is the position of the document in
+/// `contents`. The pairs are in the order of `contents`.
+#[cfg(feature = "rayon")]
+pub fn extract_metadata_batch_excluding_drafts(
+    contents: &[&str],
+) -> Vec<(usize, MetadataResult)> {
+    use rayon::prelude::*;
+
+    contents
+        .par_iter()
+        .enumerate()
+        .filter_map(|(index, content)| {
+            let result = extract_and_prepare_metadata(content);
+            match &result {
+                Ok((metadata, _, _))
+                    if metadata::is_draft(&Metadata::new(
+                        metadata.clone(),
+                    )) =>
+                {
+                    None
+                }
+                _ => Some((index, result)),
+            }
+        })
+        .collect()
+}
+
+/// Extracts and prepares metadata as a single JSON document.
+///
+/// This combines the outputs of `extract_and_prepare_metadata` into one
+/// JSON object of the form
+/// `{ "metadata": {...}, "keywords": [...], "meta_tags": {...} }`, where
+/// the metadata keys are sorted and each meta tag group is a named string
+/// field. This is convenient for shell scripts and other non-Rust callers.
+///
+/// # Arguments
+///
+/// * `content` - A string slice representing the content from which to extract metadata.
+///
+/// # Returns
+///
+/// Returns a Result containing the JSON document as a string.
+///
+/// # Errors
+///
+/// This function will return a `MetadataError` if metadata extraction fails
+/// or the result cannot be serialized.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::extract_and_prepare_metadata_json;
+///
+/// let content = "---\ntitle: My Page\nkeywords: rust, seo\n---\n";
+/// let json = extract_and_prepare_metadata_json(content).unwrap();
+/// assert!(json.contains(r#""keywords":["rust","seo"]"#));
+/// ```
+pub fn extract_and_prepare_metadata_json(
+    content: &str,
+) -> Result<String, MetadataError> {
+    let (metadata, keywords, meta_tags) =
+        extract_and_prepare_metadata(content)?;
+    let metadata: BTreeMap<String, String> =
+        metadata.into_iter().collect();
+
+    let summary = serde_json::json!({
+        "metadata": metadata,
+        "keywords": keywords,
+        "meta_tags": meta_tags,
+    });
+    Ok(serde_json::to_string(&summary)?)
+}
+
 /// Extracts keywords from the metadata.
 ///
 /// This function looks for a "keywords" key in the metadata and splits its value into a vector of strings.
+/// It is equivalent to `extract_keywords_multi` with only the "keywords" field.
 ///
 /// # Arguments
 ///
@@ -101,16 +350,183 @@ pub fn extract_and_prepare_metadata(content: &str) -> MetadataResult {
 pub fn extract_keywords(
     metadata: &HashMap<String, String>,
 ) -> Vec<String> {
-    metadata
-        .get("keywords")
-        .map(|k| k.split(',').map(|s| s.trim().to_string()).collect())
-        .unwrap_or_default()
+    extract_keywords_multi(metadata, &["keywords"])
+}
+
+/// The fields read by `extract_keywords_multi` by default.
+pub const DEFAULT_KEYWORD_FIELDS: [&str; 2] = ["keywords", "tags"];
+
+/// Extracts and merges keywords from several metadata fields.
+///
+/// Each field is split on commas, and inline lists such as `[a, b]` are
+/// unwrapped first. The keywords are trimmed, empty ones are skipped, and
+/// a keyword already seen in an earlier field or position is dropped, so
+/// the result keeps the order of first occurrence.
+///
+/// # Arguments
+///
+/// * `metadata` - A reference to a HashMap containing the metadata.
+/// * `fields` - The fields to read, in order, such as
+///   `DEFAULT_KEYWORD_FIELDS`.
+///
+/// # Returns
+///
+/// A vector of the distinct keywords.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::{extract_keywords_multi, DEFAULT_KEYWORD_FIELDS};
+/// use std::collections::HashMap;
+///
+/// let mut metadata = HashMap::new();
+/// metadata.insert("keywords".to_string(), "rust, seo".to_string());
+/// metadata.insert("tags".to_string(), "[seo, web]".to_string());
+///
+/// assert_eq!(
+///     extract_keywords_multi(&metadata, &DEFAULT_KEYWORD_FIELDS),
+///     vec!["rust", "seo", "web"]
+/// );
+/// ```
+pub fn extract_keywords_multi(
+    metadata: &HashMap<String, String>,
+    fields: &[&str],
+) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut keywords = Vec::new();
+
+    for value in fields.iter().filter_map(|field| metadata.get(*field))
+    {
+        let value = metadata::inline_list_to_comma_separated(value);
+        for keyword in value.split(',').map(str::trim) {
+            if !keyword.is_empty() && seen.insert(keyword.to_string()) {
+                keywords.push(keyword.to_string());
+            }
+        }
+    }
+    keywords
+}
+
+/// Common English words ignored when counting body terms.
+const STOP_WORDS: &[&str] = &[
+    "about", "after", "all", "also", "and", "are", "but", "can", "for",
+    "from", "has", "have", "how", "into", "its", "more", "not", "our",
+    "that", "the", "their", "them", "then", "there", "these", "this",
+    "was", "were", "what", "when", "which", "will", "with", "you",
+    "your",
+];
+
+/// Counts keyword frequencies for weighted keyword displays.
+///
+/// Each declared keyword from the "keywords" key counts once, plus once
+/// for every whole-word occurrence in `content`. When `include_body` is
+/// `true`, the other words of `content` are counted as well, ignoring
+/// common English words, numbers, and words shorter than three letters.
+/// Body terms are lowercase; declared keywords keep their declared form.
+///
+/// # Arguments
+///
+/// * `metadata` - A reference to a HashMap containing the metadata.
+/// * `content` - The body text to count terms in.
+/// * `include_body` - Whether to include body terms that are not declared keywords.
+///
+/// # Returns
+///
+/// The `(keyword, count)` pairs, sorted by count descending and then
+/// alphabetically.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::extract_keyword_counts;
+/// use std::collections::HashMap;
+///
+/// let mut metadata = HashMap::new();
+/// metadata.insert("keywords".to_string(), "rust, seo".to_string());
+///
+/// let counts = extract_keyword_counts(&metadata, "Rust is fast.", false);
+/// assert_eq!(counts, vec![("rust".to_string(), 2), ("seo".to_string(), 1)]);
+/// ```
+pub fn extract_keyword_counts(
+    metadata: &HashMap<String, String>,
+    content: &str,
+    include_body: bool,
+) -> Vec<(String, usize)> {
+    let mut declared = extract_keywords(metadata);
+    declared.retain(|keyword| !keyword.is_empty());
+    let mut seen = HashSet::new();
+    declared.retain(|keyword| seen.insert(keyword.to_lowercase()));
+
+    let mut counts: Vec<(String, usize)> =
+        seo::keyword_density(&declared, content)
+            .into_iter()
+            .map(|(keyword, count, _)| (keyword, count + 1))
+            .collect();
+
+    if include_body {
+        let mut terms: HashMap<String, usize> = HashMap::new();
+        for word in seo::tokenize(content) {
+            if word.chars().count() < 3
+                || word.chars().all(|c| c.is_numeric())
+                || STOP_WORDS.contains(&word.as_str())
+                || seen.contains(&word)
+            {
+                continue;
+            }
+            *terms.entry(word).or_insert(0) += 1;
+        }
+        counts.extend(terms);
+    }
+
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_extract_keyword_counts_declared_only() {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "keywords".to_string(),
+            "Rust, static site, rust, seo".to_string(),
+        );
+        let content = "Rust builds a static site. Rust is fast.";
+
+        let counts = extract_keyword_counts(&metadata, content, false);
+        assert_eq!(
+            counts,
+            vec![
+                ("Rust".to_string(), 3),
+                ("static site".to_string(), 2),
+                ("seo".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_keyword_counts_with_body() {
+        let mut metadata = HashMap::new();
+        metadata.insert("keywords".to_string(), "rust".to_string());
+        let content =
+            "Metadata and more metadata: the rust crate parses metadata in 2024.";
+
+        let counts = extract_keyword_counts(&metadata, content, true);
+        assert_eq!(counts[0], ("metadata".to_string(), 3));
+        assert_eq!(counts[1], ("rust".to_string(), 2));
+        assert!(counts.contains(&("crate".to_string(), 1)));
+        assert!(counts.contains(&("parses".to_string(), 1)));
+        assert!(!counts.iter().any(|(word, _)| {
+            ["and", "the", "in", "2024", "more"]
+                .contains(&word.as_str())
+        }));
+
+        let counts = extract_keyword_counts(&HashMap::new(), "", true);
+        assert!(counts.is_empty());
+    }
+
     #[test]
     fn test_extract_and_prepare_metadata() {
         let content = r#"---
@@ -137,6 +553,240 @@ fn test_extract_and_prepare_metadata() {
         assert!(!meta_tags.primary.is_empty());
     }
 
+    #[test]
+    fn test_extract_and_process_metadata() {
+        let content = r#"---
+title: Test Page
+date: 20/05/2023
+keywords: test, metadata
+---
+# Test Content"#;
+
+        let (metadata, keywords, _) =
+            extract_and_process_metadata(content).unwrap();
+        assert_eq!(metadata.get("date").unwrap(), "2023-05-20");
+        assert_eq!(metadata.get("slug").unwrap(), "test-page");
+        assert_eq!(keywords, vec!["test", "metadata"]);
+
+        let (raw, _, _) =
+            extract_and_prepare_metadata(content).unwrap();
+        assert_eq!(raw.get("date").unwrap(), "20/05/2023");
+        assert!(!raw.contains_key("slug"));
+    }
+
+    #[test]
+    fn test_extract_and_process_metadata_missing_date() {
+        let content = "---\ntitle: No Date\n---\nBody";
+
+        assert!(matches!(
+            extract_and_process_metadata(content),
+            Err(MetadataError::MissingFieldError(field)) if field == "date"
+        ));
+        assert!(extract_and_prepare_metadata(content).is_ok());
+    }
+
+    #[test]
+    fn test_extract_and_prepare_metadata_json() {
+        let content = r#"---
+title: Test Page
+description: A test page
+keywords: test, json
+---
+# Test Content"#;
+
+        let json = extract_and_prepare_metadata_json(content).unwrap();
+        let value: serde_json::Value =
+            serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["metadata"]["title"], "Test Page");
+        assert_eq!(value["metadata"]["description"], "A test page");
+        assert_eq!(
+            value["keywords"],
+            serde_json::json!(["test", "json"])
+        );
+        assert_eq!(
+            value["meta_tags"]["primary"],
+            "<meta name=\"description\" content=\"A test page\">\n\
+             <meta name=\"keywords\" content=\"test, json\">"
+        );
+        for group in [
+            "apple", "primary", "og", "ms", "twitter", "links", "icons",
+        ] {
+            assert!(value["meta_tags"][group].is_string(), "{}", group);
+        }
+        assert!(value["meta_tags"].get("www_policy").is_none());
+        assert!(value["meta_tags"].get("self_closing").is_none());
+    }
+
+    #[test]
+    fn test_extract_and_prepare_metadata_json_error() {
+        assert!(matches!(
+            extract_and_prepare_metadata_json("No front matter"),
+            Err(MetadataError::ExtractionError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_extract_and_process_metadata_generates_description() {
+        let content = "---\ntitle: My Page\ndate: 2023-05-20\n---\n# My Page\n\nThe *first* paragraph.\n\nThe second.";
+        let (metadata, _, meta_tags) =
+            extract_and_process_metadata(content).unwrap();
+        assert_eq!(
+            metadata.get("description").unwrap(),
+            "The first paragraph."
+        );
+        assert!(meta_tags.primary.contains("The first paragraph."));
+
+        let content = "---\ntitle: My Page\ndate: 2023-05-20\ndescription: Kept\n---\nBody text.";
+        let (metadata, _, _) =
+            extract_and_process_metadata(content).unwrap();
+        assert_eq!(metadata.get("description").unwrap(), "Kept");
+    }
+
+    #[test]
+    fn test_extract_and_prepare_metadata_verbose() {
+        let content = "---
+title: Old
+description: A page
+title: New
+---
+Body";
+        assert!(extract_and_prepare_metadata(content).is_err());
+
+        let (result, warnings) =
+            extract_and_prepare_metadata_verbose(content);
+        let (metadata, _, meta_tags) = result.unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "New");
+        assert!(meta_tags.primary.contains("A page"));
+        assert_eq!(
+            warnings,
+            vec!["title: duplicate key, last value kept"]
+        );
+    }
+
+    #[cfg(feature = "whatlang")]
+    #[test]
+    fn test_extract_and_process_metadata_detects_lang() {
+        let content =
+            "---\ntitle: Mon article\ndate: 2023-05-20\n---\n\
+            Le renard brun rapide saute par-dessus le chien paresseux \
+            et court dans la forêt avant la tombée de la nuit.";
+        let (metadata, _, _) =
+            extract_and_process_metadata(content).unwrap();
+        assert_eq!(metadata.get("lang").unwrap(), "fr");
+
+        let content = "---\ntitle: Mon article\ndate: 2023-05-20\nlang: fr-CA\n---\n\
+            The quick brown fox jumps over the lazy dog every morning.";
+        let (metadata, _, _) =
+            extract_and_process_metadata(content).unwrap();
+        assert_eq!(metadata.get("lang").unwrap(), "fr-CA");
+    }
+
+    #[cfg(feature = "whatlang")]
+    #[test]
+    fn test_extract_and_process_metadata_detects_lang_from_body() {
+        let content = "---\n\
+            title: The weekly news from our team and all of the things \
+            that we have been working on\n\
+            date: 2023-05-20\n\
+            description: A summary of everything that happened this week \
+            in the office and at home with the family\n\
+            keywords: [news, weekly, team, update, summary]\n---\n\
+            Der schnelle braune Fuchs springt über den faulen Hund \
+            und läuft durch den Wald.";
+        assert_ne!(
+            utils::detect_language(content).as_deref(),
+            Some("de")
+        );
+
+        let (metadata, _, _) =
+            extract_and_process_metadata(content).unwrap();
+        assert_eq!(metadata.get("lang").unwrap(), "de");
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_extract_metadata_batch() {
+        let contents = [
+            "---\ntitle: First\n---\nBody",
+            "No front matter",
+            "{\"title\": \"Second\"}\nBody",
+            "---\ntitle: Third\n---\nBody",
+            "---\ninvalid: [\n---\nBody",
+        ];
+
+        let results = extract_metadata_batch(&contents);
+        assert_eq!(results.len(), contents.len());
+
+        let titles: Vec<Option<String>> = results
+            .iter()
+            .map(|result| {
+                result.as_ref().ok().map(|(metadata, _, _)| {
+                    metadata.get("title").cloned().unwrap_or_default()
+                })
+            })
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                Some("First".to_string()),
+                None,
+                Some("Second".to_string()),
+                Some("Third".to_string()),
+                None,
+            ]
+        );
+        for (result, content) in results.iter().zip(contents) {
+            assert_eq!(
+                result.is_ok(),
+                extract_and_prepare_metadata(content).is_ok()
+            );
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_extract_metadata_batch_excluding_drafts() {
+        let contents = [
+            "---\ntitle: Draft\ndraft: true\n---\nBody",
+            "---\ntitle: Published\ndraft: false\n---\nBody",
+            "No front matter",
+            "---\ntitle: Undated\n---\nBody",
+            "{\"title\": \"JSON draft\", \"draft\": true}\nBody",
+        ];
+
+        let results =
+            extract_metadata_batch_excluding_drafts(&contents);
+        let indices: Vec<usize> =
+            results.iter().map(|(index, _)| *index).collect();
+        assert_eq!(indices, vec![1, 2, 3]);
+
+        assert_eq!(
+            results[0].1.as_ref().unwrap().0.get("title").unwrap(),
+            "Published"
+        );
+        assert!(results[1].1.is_err());
+        assert_eq!(
+            results[2].1.as_ref().unwrap().0.get("title").unwrap(),
+            "Undated"
+        );
+    }
+
+    #[test]
+    fn test_extract_and_prepare_metadata_list_keywords() {
+        let content = "---\ntitle: Lists\nkeywords:\n  - a\n  - b\n  - c\ntags: [x, y]\n---\nBody";
+
+        let (metadata, keywords, meta_tags) =
+            extract_and_prepare_metadata(content).unwrap();
+        assert_eq!(metadata.get("keywords").unwrap(), "a, b, c");
+        assert_eq!(metadata.get("tags").unwrap(), "x, y");
+        assert_eq!(keywords, vec!["a", "b", "c"]);
+        assert!(meta_tags
+            .primary
+            .contains(r#"<meta name="keywords" content="a, b, c">"#));
+        assert!(!meta_tags.primary.contains('['));
+    }
+
     #[test]
     fn test_extract_keywords() {
         let mut metadata = HashMap::new();
@@ -149,6 +799,44 @@ fn test_extract_keywords() {
         assert_eq!(keywords, vec!["rust", "programming", "metadata"]);
     }
 
+    #[test]
+    fn test_extract_keywords_multi() {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "keywords".to_string(),
+            "rust, metadata, seo".to_string(),
+        );
+        metadata
+            .insert("tags".to_string(), "[seo, web, rust]".to_string());
+        metadata
+            .insert("categories".to_string(), "ignored".to_string());
+
+        assert_eq!(
+            extract_keywords_multi(&metadata, &DEFAULT_KEYWORD_FIELDS),
+            vec!["rust", "metadata", "seo", "web"]
+        );
+        assert_eq!(
+            extract_keywords_multi(&metadata, &["tags", "keywords"]),
+            vec!["seo", "web", "rust", "metadata"]
+        );
+        assert!(
+            extract_keywords_multi(&metadata, &["missing"]).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_extract_keywords_multi_skips_empty() {
+        let mut metadata = HashMap::new();
+        metadata
+            .insert("keywords".to_string(), "a, , a,b ,".to_string());
+        metadata.insert("tags".to_string(), String::new());
+
+        assert_eq!(
+            extract_keywords_multi(&metadata, &DEFAULT_KEYWORD_FIELDS),
+            vec!["a", "b"]
+        );
+    }
+
     #[test]
     fn test_extract_keywords_empty() {
         let metadata = HashMap::new();
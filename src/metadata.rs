@@ -7,8 +7,16 @@
 use dtt::datetime::DateTime;
 use regex::Regex;
 use serde_json::Value as JsonValue;
-use std::collections::HashMap;
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
 use toml::Value as TomlValue;
+use yaml_rust2::{
+    parser::{Event, Parser},
+    scanner::TScalarStyle,
+};
 
 /// Represents metadata for a page or content item.
 #[derive(Debug, Default, Clone)]
@@ -43,6 +51,205 @@ pub fn get(&self, key: &str) -> Option<&String> {
         self.inner.get(key)
     }
 
+    /// Retrieves the value associated with the given key, if it is not empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A string slice representing the key to look up.
+    ///
+    /// # Returns
+    ///
+    /// The value if the key exists and the value contains a non-whitespace
+    /// character, or `None` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metadata_gen::metadata::Metadata;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut metadata = Metadata::new(HashMap::new());
+    /// metadata.insert("description".to_string(), "  ".to_string());
+    /// assert!(metadata.get_non_empty("description").is_none());
+    /// ```
+    pub fn get_non_empty(&self, key: &str) -> Option<&String> {
+        match self.inner.get(key) {
+            Some(value) if !value.trim().is_empty() => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Retrieves the value associated with the given key, or a default.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A string slice representing the key to look up.
+    /// * `default` - The value to return if the key does not exist.
+    ///
+    /// # Returns
+    ///
+    /// The value if the key exists, or `default` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metadata_gen::metadata::Metadata;
+    /// use std::collections::HashMap;
+    ///
+    /// let metadata = Metadata::new(HashMap::new());
+    /// assert_eq!(metadata.get_or("layout", "page"), "page");
+    /// ```
+    pub fn get_or<'a>(
+        &'a self,
+        key: &str,
+        default: &'a str,
+    ) -> &'a str {
+        self.inner.get(key).map_or(default, String::as_str)
+    }
+
+    /// Retrieves an owned copy of the value associated with the given key,
+    /// or a default.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A string slice representing the key to look up.
+    /// * `default` - The value to return if the key does not exist.
+    ///
+    /// # Returns
+    ///
+    /// A clone of the value if the key exists, or `default` otherwise.
+    pub fn get_owned_or(&self, key: &str, default: String) -> String {
+        self.inner.get(key).cloned().unwrap_or(default)
+    }
+
+    /// Retrieves a list value as its individual elements.
+    ///
+    /// Lists are flattened to an inline `[a, b]` form during extraction,
+    /// with elements that contain a comma or a double quote wrapped in
+    /// double quotes. This accessor reverses that encoding, so an element
+    /// such as `"a, b"` is returned intact rather than split in two.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A string slice representing the key to look up.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the key does not exist, the list elements if the value is
+    /// an inline list, or a single element holding the value otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metadata_gen::metadata::extract_metadata;
+    ///
+    /// let content = "---\ntags: [\"a, b\", c]\n---\n";
+    /// let metadata = extract_metadata(content).unwrap();
+    /// assert_eq!(metadata.get_list("tags").unwrap(), vec!["a, b", "c"]);
+    /// ```
+    pub fn get_list(&self, key: &str) -> Option<Vec<String>> {
+        self.inner.get(key).map(|value| parse_inline_list(value))
+    }
+
+    /// Retrieves a value as a boolean.
+    ///
+    /// The value is trimmed and compared case-insensitively: `true`, `yes`,
+    /// `on`, and `1` are `true`, and `false`, `no`, `off`, and `0` are
+    /// `false`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A string slice representing the key to look up.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the key does not exist or its value is not a recognized
+    /// boolean.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metadata_gen::metadata::Metadata;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut metadata = Metadata::new(HashMap::new());
+    /// metadata.insert("comments".to_string(), "Yes".to_string());
+    /// assert_eq!(metadata.get_bool("comments"), Some(true));
+    /// ```
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        let value = self.inner.get(key)?.trim().to_ascii_lowercase();
+        match value.as_str() {
+            "true" | "yes" | "on" | "1" => Some(true),
+            "false" | "no" | "off" | "0" => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Retrieves a nested value by its path.
+    ///
+    /// Nested mappings are flattened to dotted keys during extraction, so
+    /// the path segments are joined with `.` to form the key.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The segments of the path, such as `["author", "email"]`.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing a reference to the value if the key exists, or `None` if it doesn't.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metadata_gen::metadata::extract_metadata;
+    ///
+    /// let content = "---\nauthor:\n  email: jane@example.com\n---\n";
+    /// let metadata = extract_metadata(content).unwrap();
+    /// assert_eq!(
+    ///     metadata.get_path(&["author", "email"]).unwrap(),
+    ///     "jane@example.com"
+    /// );
+    /// ```
+    pub fn get_path(&self, path: &[&str]) -> Option<&String> {
+        self.inner.get(&path.join("."))
+    }
+
+    /// Returns the entries nested under a prefix, with the prefix removed.
+    ///
+    /// An entry is nested under `prefix` if its key starts with `prefix`
+    /// followed by `.`. A trailing `.` on `prefix` is optional.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The key of the parent mapping, such as `author`.
+    ///
+    /// # Returns
+    ///
+    /// A `HashMap` of the nested keys, relative to `prefix`, to their values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metadata_gen::metadata::extract_metadata;
+    ///
+    /// let content = "---\nauthor:\n  name: Jane\n  social:\n    x: \"@jane\"\n---\n";
+    /// let metadata = extract_metadata(content).unwrap();
+    /// let author = metadata.get_subtree("author");
+    /// assert_eq!(author["name"], "Jane");
+    /// assert_eq!(author["social.x"], "@jane");
+    /// ```
+    pub fn get_subtree(&self, prefix: &str) -> HashMap<String, String> {
+        let prefix = prefix.strip_suffix('.').unwrap_or(prefix);
+        self.inner
+            .iter()
+            .filter_map(|(key, value)| {
+                let rest =
+                    key.strip_prefix(prefix)?.strip_prefix('.')?;
+                Some((rest.to_string(), value.clone()))
+            })
+            .collect()
+    }
+
     /// Inserts a key-value pair into the metadata.
     ///
     /// # Arguments
@@ -74,6 +281,140 @@ pub fn contains_key(&self, key: &str) -> bool {
         self.inner.contains_key(key)
     }
 
+    /// Returns a reference to the inner `HashMap`.
+    ///
+    /// Unlike `into_inner`, this does not consume the `Metadata` instance.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the inner `HashMap<String, String>`.
+    pub fn as_map(&self) -> &HashMap<String, String> {
+        &self.inner
+    }
+
+    /// Returns the key-value pairs sorted by key.
+    ///
+    /// Unlike iterating over `as_map`, the order is deterministic, which
+    /// makes it suitable for snapshot tests and reproducible output.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<(String, String)>` of the pairs in ascending key order.
+    pub fn sorted_pairs(&self) -> Vec<(String, String)> {
+        let mut pairs: Vec<(String, String)> = self
+            .inner
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        pairs.sort_by(|a, b| a.0.cmp(&b.0));
+        pairs
+    }
+
+    /// Computes a stable hash of the metadata.
+    ///
+    /// The pairs are hashed in the order of `sorted_pairs` with the 64-bit
+    /// FNV-1a hash, so the result does not depend on insertion order and
+    /// is the same across runs and platforms. It is not cryptographically
+    /// secure, but suits change detection and HTTP `ETag` values.
+    ///
+    /// # Returns
+    ///
+    /// The hash as a 16-character lowercase hexadecimal string.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metadata_gen::metadata::Metadata;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut a = Metadata::new(HashMap::new());
+    /// a.insert("title".to_string(), "Hello".to_string());
+    /// a.insert("lang".to_string(), "en".to_string());
+    ///
+    /// let mut b = Metadata::new(HashMap::new());
+    /// b.insert("lang".to_string(), "en".to_string());
+    /// b.insert("title".to_string(), "Hello".to_string());
+    ///
+    /// assert_eq!(a.content_hash(), b.content_hash());
+    /// assert_eq!(a.content_hash().len(), 16);
+    /// ```
+    pub fn content_hash(&self) -> String {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let mut hash = FNV_OFFSET_BASIS;
+        let mut write = |bytes: &[u8]| {
+            for &byte in bytes {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        };
+        for (key, value) in self.sorted_pairs() {
+            // Length prefixes keep `("ab", "c")` and `("a", "bc")` apart.
+            write(&(key.len() as u64).to_le_bytes());
+            write(key.as_bytes());
+            write(&(value.len() as u64).to_le_bytes());
+            write(value.as_bytes());
+        }
+        format!("{:016x}", hash)
+    }
+
+    /// Serializes the metadata as a TOML front matter block.
+    ///
+    /// The block is delimited by `+++` lines. Dotted keys such as
+    /// `author.name` are reconstructed into `[author]` tables. Values are
+    /// written as follows:
+    /// - An inline list of several items, as produced by flattening a
+    ///   YAML, TOML, or JSON array, such as `[rust, seo]`, becomes a TOML
+    ///   array of strings. A bracketed value with a single item, such as
+    ///   `[WIP]`, cannot be told apart from a string and is written as one.
+    /// - `true` and `false` become booleans.
+    /// - Integers and floats become numbers when they are written exactly
+    ///   as TOML would write them back, so `42` and `1.5` are numbers but
+    ///   `007` and `1.50` are strings.
+    /// - All other values are written as strings.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the front matter block.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MetadataError::TomlError` if a key is used both as a value
+    /// and as a table, such as `author` and `author.name`, or if
+    /// serialization fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metadata_gen::metadata::Metadata;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut metadata = Metadata::new(HashMap::new());
+    /// metadata.insert("title".to_string(), "Hello".to_string());
+    /// metadata.insert("tags".to_string(), "[rust, seo]".to_string());
+    /// metadata.insert("draft".to_string(), "true".to_string());
+    ///
+    /// assert_eq!(
+    ///     metadata.to_toml_front_matter().unwrap(),
+    ///     "+++\ndraft = true\ntags = [\"rust\", \"seo\"]\ntitle = \"Hello\"\n+++\n"
+    /// );
+    /// ```
+    pub fn to_toml_front_matter(
+        &self,
+    ) -> Result<String, MetadataError> {
+        let mut table = toml::Table::new();
+        for (key, value) in self.sorted_pairs() {
+            let value = to_toml_value(value);
+            let path = key.split('.').collect::<Vec<_>>();
+            insert_toml_path(&mut table, &key, &path, value)?;
+        }
+
+        let body = toml::to_string(&TomlValue::Table(table))
+            .map_err(|e| toml_error(e.to_string()))?;
+        Ok(format!("+++\n{}+++\n", body))
+    }
+
     /// Consumes the `Metadata` instance and returns the inner `HashMap`.
     ///
     /// # Returns
@@ -82,11 +423,361 @@ pub fn contains_key(&self, key: &str) -> bool {
     pub fn into_inner(self) -> HashMap<String, String> {
         self.inner
     }
+
+    /// Removes derived fields so they are regenerated on reprocessing.
+    ///
+    /// Pass `DEFAULT_DERIVED_KEYS` to remove the fields derived by
+    /// this crate, or a custom list of keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The derived keys to remove.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metadata_gen::metadata::{Metadata, DEFAULT_DERIVED_KEYS};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut metadata = Metadata::new(HashMap::new());
+    /// metadata.insert("title".to_string(), "Hello".to_string());
+    /// metadata.insert("slug".to_string(), "hello".to_string());
+    ///
+    /// metadata.clear_derived(&DEFAULT_DERIVED_KEYS);
+    /// assert!(!metadata.contains_key("slug"));
+    /// assert!(metadata.contains_key("title"));
+    /// ```
+    pub fn clear_derived(&mut self, keys: &[&str]) {
+        for key in keys {
+            self.inner.remove(*key);
+        }
+    }
+
+    /// Normalizes the keys by trimming them and collapsing whitespace.
+    ///
+    /// Surrounding whitespace is removed from each key, and each run of
+    /// inner whitespace is replaced with a single space, so that a key
+    /// written as `"title "` can be looked up as `title`. When several keys
+    /// normalize to the same key, the keys are applied in alphabetical order
+    /// of their original spelling and the last one wins.
+    ///
+    /// # Returns
+    ///
+    /// The number of keys that were changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metadata_gen::metadata::Metadata;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut metadata = Metadata::new(HashMap::new());
+    /// metadata.insert(" title ".to_string(), "Hello".to_string());
+    ///
+    /// assert_eq!(metadata.normalize_keys(), 1);
+    /// assert_eq!(metadata.get("title").unwrap(), "Hello");
+    /// ```
+    pub fn normalize_keys(&mut self) -> usize {
+        let mut changed = 0;
+        let mut normalized = HashMap::with_capacity(self.inner.len());
+        for (key, value) in self.sorted_pairs() {
+            let new_key =
+                key.split_whitespace().collect::<Vec<_>>().join(" ");
+            if new_key != key {
+                changed += 1;
+            }
+            normalized.insert(new_key, value);
+        }
+        self.inner = normalized;
+        changed
+    }
+
+    /// Retains only the entries for which the predicate returns `true`.
+    ///
+    /// This mirrors `HashMap::retain`, passing each key and value to `f`.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The predicate, called with each key and value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metadata_gen::metadata::Metadata;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut metadata = Metadata::new(HashMap::new());
+    /// metadata.insert("title".to_string(), "Hello".to_string());
+    /// metadata.insert("layout".to_string(), "post".to_string());
+    ///
+    /// metadata.retain(|key, _| key != "layout");
+    /// assert!(metadata.contains_key("title"));
+    /// assert!(!metadata.contains_key("layout"));
+    /// ```
+    pub fn retain<F: FnMut(&str, &str) -> bool>(&mut self, mut f: F) {
+        self.inner.retain(|key, value| f(key, value));
+    }
+
+    /// Fills in missing fields from a map of defaults.
+    ///
+    /// Each default is inserted only if its key is absent, so existing
+    /// values, including empty ones, are never overwritten. This suits
+    /// site-wide defaults such as `author` or `og:type`.
+    ///
+    /// # Arguments
+    ///
+    /// * `defaults` - A map from key to default value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metadata_gen::metadata::Metadata;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut metadata = Metadata::new(HashMap::new());
+    /// metadata.insert("author".to_string(), "Jane".to_string());
+    ///
+    /// let mut defaults = HashMap::new();
+    /// defaults.insert("author".to_string(), "Site Team".to_string());
+    /// defaults.insert("og:type".to_string(), "website".to_string());
+    ///
+    /// metadata.apply_defaults(&defaults);
+    /// assert_eq!(metadata.get("author").unwrap(), "Jane");
+    /// assert_eq!(metadata.get("og:type").unwrap(), "website");
+    /// ```
+    pub fn apply_defaults(
+        &mut self,
+        defaults: &HashMap<String, String>,
+    ) {
+        for (key, value) in defaults {
+            if !self.inner.contains_key(key) {
+                self.inner.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    /// Renames alias keys to their canonical keys.
+    ///
+    /// Aliases are applied in the alphabetical order of the alias keys, and
+    /// each renamed value overwrites any existing value of its canonical key
+    /// (last writer wins). For example, with the default table, when both
+    /// `desc` and `summary` are present, `summary` is applied last and its
+    /// value becomes the `description`.
+    ///
+    /// # Arguments
+    ///
+    /// * `aliases` - A map from alias key to canonical key, such as the one
+    ///   returned by `default_aliases`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metadata_gen::metadata::{default_aliases, Metadata};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut metadata = Metadata::new(HashMap::new());
+    /// metadata.insert("summary".to_string(), "A page".to_string());
+    /// metadata.apply_aliases(&default_aliases());
+    /// assert_eq!(metadata.get("description").unwrap(), "A page");
+    /// assert!(!metadata.contains_key("summary"));
+    /// ```
+    pub fn apply_aliases(&mut self, aliases: &HashMap<String, String>) {
+        let mut aliases = aliases.iter().collect::<Vec<_>>();
+        aliases.sort();
+
+        for (alias, canonical) in aliases {
+            if alias == canonical {
+                continue;
+            }
+            if let Some(value) = self.inner.remove(alias) {
+                self.inner.insert(canonical.clone(), value);
+            }
+        }
+    }
+
+    /// Splits the metadata into recognized and custom fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `known` - The keys that are considered recognized.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of `(known, extra)` maps, where `known` holds the entries
+    /// whose keys appear in `known` and `extra` holds all other entries.
+    pub fn partition_known(
+        &self,
+        known: &[&str],
+    ) -> (HashMap<String, String>, HashMap<String, String>) {
+        self.inner
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .partition(|(k, _)| known.contains(&k.as_str()))
+    }
+}
+
+/// The keys of fields derived during processing.
+pub const DEFAULT_DERIVED_KEYS: [&str; 3] =
+    ["slug", "word_count", "reading_time"];
+
+/// The default alias table, as `(alias, canonical)` pairs.
+pub const DEFAULT_ALIASES: [(&str, &str); 4] = [
+    ("desc", "description"),
+    ("summary", "description"),
+    ("tags", "keywords"),
+    ("published", "date"),
+];
+
+/// Returns the default alias table as a map from alias to canonical key.
+///
+/// The returned map can be extended with additional aliases before being
+/// passed to `Metadata::apply_aliases`.
+///
+/// # Returns
+///
+/// A `HashMap` built from `DEFAULT_ALIASES`.
+pub fn default_aliases() -> HashMap<String, String> {
+    DEFAULT_ALIASES
+        .iter()
+        .map(|(alias, canonical)| {
+            (alias.to_string(), canonical.to_string())
+        })
+        .collect()
+}
+
+/// The default maximum nesting depth accepted when flattening front matter.
+pub const DEFAULT_MAX_DEPTH: usize = 32;
+
+/// The opening and closing delimiters of a front matter block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delimiters {
+    /// The delimiter that opens the front matter block.
+    pub open: String,
+    /// The delimiter that closes the front matter block.
+    pub close: String,
+}
+
+impl Delimiters {
+    /// Creates a new `Delimiters` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `open` - The delimiter that opens the front matter block.
+    /// * `close` - The delimiter that closes the front matter block.
+    ///
+    /// # Returns
+    ///
+    /// A new `Delimiters` instance.
+    pub fn new(
+        open: impl Into<String>,
+        close: impl Into<String>,
+    ) -> Self {
+        Delimiters {
+            open: open.into(),
+            close: close.into(),
+        }
+    }
+
+    /// Checks that neither delimiter is empty.
+    fn validate(&self, field: &str) -> Result<(), MetadataError> {
+        if self.open.trim().is_empty() || self.close.trim().is_empty() {
+            return Err(MetadataError::new_validation_error(
+                field,
+                "Front matter delimiters must not be empty",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The serialization formats supported for front matter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrontMatterFormat {
+    /// YAML front matter.
+    Yaml,
+    /// TOML front matter.
+    Toml,
+    /// JSON front matter.
+    Json,
+}
+
+/// Options controlling how front matter is extracted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractOptions {
+    /// The maximum nesting depth of mappings and tables in the front
+    /// matter. Deeper documents are rejected with a
+    /// `MetadataError::ProcessingError` instead of being flattened.
+    pub max_depth: usize,
+    /// The maximum size in bytes of a captured front matter block, or
+    /// `None` for no limit. Larger blocks are rejected with a
+    /// `MetadataError::ExtractionError` before they are parsed. The limit
+    /// also applies to a reStructuredText field list.
+    pub max_front_matter_bytes: Option<usize>,
+    /// Whether to normalize the extracted keys with
+    /// `Metadata::normalize_keys`. Defaults to `false`.
+    pub normalize_keys: bool,
+    /// Whether YAML booleans and numbers are normalized. When `true`, the
+    /// default, `True` becomes `true` and `0x10` becomes `16`. When
+    /// `false`, the original token text, such as `True` or `0x10`, is kept.
+    pub normalize_scalars: bool,
+    /// The delimiters of YAML front matter, `---` by default.
+    pub yaml_delimiters: Delimiters,
+    /// The delimiters of TOML front matter, `+++` by default.
+    pub toml_delimiters: Delimiters,
+    /// The delimiters of JSON front matter, `{` and `}` by default.
+    ///
+    /// The text between the delimiters is parsed as the members of a
+    /// JSON object, so the default braces double as the object braces.
+    pub json_delimiters: Delimiters,
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        ExtractOptions {
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_front_matter_bytes: None,
+            normalize_keys: false,
+            normalize_scalars: true,
+            yaml_delimiters: Delimiters::new("---", "---"),
+            toml_delimiters: Delimiters::new("+++", "+++"),
+            json_delimiters: Delimiters::new("{", "}"),
+        }
+    }
+}
+
+/// Checks whether the metadata marks the content as a draft.
+///
+/// The `draft` field is read with `Metadata::get_bool`, so `true`, `yes`,
+/// `on`, and `1` mark a draft. An absent or unrecognized value does not.
+///
+/// # Arguments
+///
+/// * `metadata` - A reference to the `Metadata` instance to check.
+///
+/// # Returns
+///
+/// `true` if the content is a draft.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::metadata::{extract_metadata, is_draft};
+///
+/// let metadata = extract_metadata("---\ntitle: WIP\ndraft: true\n---\n").unwrap();
+/// assert!(is_draft(&metadata));
+/// ```
+pub fn is_draft(metadata: &Metadata) -> bool {
+    metadata.get_bool("draft").unwrap_or(false)
 }
 
 /// Extracts metadata from the content string.
 ///
-/// This function attempts to extract metadata from YAML, TOML, or JSON formats.
+/// This function attempts to extract metadata from YAML, TOML, or JSON formats,
+/// falling back to a leading reStructuredText field list.
+///
+/// YAML aliases (`*name`) flatten to the values of their anchors, and
+/// `<<` merge keys copy the merged entries into the mapping, without
+/// overriding keys written out in it.
 ///
 /// # Arguments
 ///
@@ -102,554 +793,5344 @@ pub fn into_inner(self) -> HashMap<String, String> {
 pub fn extract_metadata(
     content: &str,
 ) -> Result<Metadata, MetadataError> {
-    extract_yaml_metadata(content)
-        .or_else(|| extract_toml_metadata(content))
-        .or_else(|| extract_json_metadata(content))
-        .ok_or_else(|| MetadataError::ExtractionError {
-            message: "No valid front matter found.".to_string(),
-        })
+    extract_metadata_with_options(content, &ExtractOptions::default())
 }
 
-/// Extracts YAML metadata from the content.
+/// Extracts metadata from raw bytes.
+///
+/// The bytes are decoded as UTF-8 before extraction, which avoids a
+/// separate, possibly lossy, decoding step when reading raw files.
 ///
 /// # Arguments
 ///
-/// * `content` - A string slice containing the content to extract YAML metadata from.
+/// * `content` - The bytes containing the content to extract metadata from.
 ///
 /// # Returns
 ///
-/// An `Option<Metadata>` containing the extracted metadata if successful, or `None` if extraction fails.
-fn extract_yaml_metadata(content: &str) -> Option<Metadata> {
-    let re = Regex::new(r"(?s)^\s*---\s*\n(.*?)\n\s*---\s*").ok()?;
-    let captures = re.captures(content)?;
-
-    let yaml_str = captures.get(1)?.as_str().trim();
+/// A `Result` containing the extracted `Metadata` if successful, or a `MetadataError` if extraction fails.
+///
+/// # Errors
+///
+/// Returns a `MetadataError::Utf8Error` if the bytes are not valid UTF-8,
+/// or any error returned by `extract_metadata`.
+pub fn extract_metadata_bytes(
+    content: &[u8],
+) -> Result<Metadata, MetadataError> {
+    let content = std::str::from_utf8(content)?;
+    extract_metadata(content)
+}
 
-    let yaml_value: serde_yml::Value =
-        serde_yml::from_str(yaml_str).ok()?;
+/// Extracts metadata from the content string, collecting non-fatal warnings.
+///
+/// This behaves like `extract_metadata`, but recovers from some problems
+/// in YAML front matter instead of failing, and reports them as warnings:
+/// - Duplicate top-level keys, which `extract_metadata` rejects, are
+///   resolved by keeping the last value.
+/// - Boolean values, which are stored as the strings `true` and `false`.
+/// - A `date` that cannot be standardized by `process_metadata`.
+///
+/// Each warning has the form `field: message`, naming the offending field.
+///
+/// # Arguments
+///
+/// * `content` - A string slice containing the content to extract metadata from.
+///
+/// # Returns
+///
+/// A tuple of the extraction result and the warnings.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::metadata::extract_metadata_with_warnings;
+///
+/// let content = "---\ntitle: First\ndraft: true\ntitle: Second\n---\n";
+/// let (result, warnings) = extract_metadata_with_warnings(content);
+/// assert_eq!(result.unwrap().get("title").unwrap(), "Second");
+/// assert_eq!(warnings.len(), 2);
+/// ```
+pub fn extract_metadata_with_warnings(
+    content: &str,
+) -> (Result<Metadata, MetadataError>, Vec<String>) {
+    let mut warnings = Vec::new();
+    let content = normalize_content(content);
+
+    let options = ExtractOptions::default();
+    let mut recovered = None;
+    if let Some(block) = yaml_block(&content, &options) {
+        let duplicates = duplicate_yaml_keys(block);
+        for key in &duplicates {
+            warnings.push(format!(
+                "{}: duplicate key, last value kept",
+                key
+            ));
+        }
+        let value = if duplicates.is_empty() {
+            serde_yml::from_str::<serde_yml::Value>(block).ok()
+        } else {
+            serde_yml::from_str::<LastValueMapping>(block)
+                .ok()
+                .map(|mapping| serde_yml::Value::Mapping(mapping.0))
+        };
+        if let Some(value) = &value {
+            collect_yaml_bool_warnings(value, "", &mut warnings);
+        }
+        if !duplicates.is_empty() {
+            recovered =
+                parse_yaml_block(block, &options, true).transpose();
+        }
+    }
 
-    let metadata: HashMap<String, String> = flatten_yaml(&yaml_value);
+    let result = match recovered {
+        Some(result) => result,
+        None => extract_metadata(&content),
+    };
+    if let Ok(metadata) = &result {
+        if let Some(date) = metadata.get("date") {
+            if let Err(e) = standardize_date(date, &DttDateParser) {
+                warnings.push(format!(
+                    "date: `{}` cannot be standardized ({})",
+                    date, e
+                ));
+            }
+        }
+    }
 
-    Some(Metadata::new(metadata))
+    (result, warnings)
 }
 
-fn flatten_yaml(value: &serde_yml::Value) -> HashMap<String, String> {
-    let mut map = HashMap::new();
-    flatten_yaml_recursive(value, String::new(), &mut map);
-    map
+/// Returns the top-level keys that appear more than once in a YAML block.
+///
+/// The keys are read from the parser events of the top-level mapping, so
+/// quoted keys such as `"a:b"` and `"title"` are compared on their value.
+/// The keys are returned in the order their first duplicate was seen.
+fn duplicate_yaml_keys(block: &str) -> Vec<String> {
+    let mut parser = Parser::new_from_str(block);
+    let mut seen = HashSet::new();
+    let mut duplicates = Vec::new();
+    // The nesting depth of the next event; the top-level mapping is 1.
+    let mut depth = 0usize;
+    let mut root_is_mapping = false;
+    // Whether the next node of the top-level mapping is a key.
+    let mut expect_key = true;
+
+    while let Ok((event, _)) = parser.next_token() {
+        match event {
+            Event::StreamEnd => break,
+            Event::MappingStart(..) | Event::SequenceStart(..) => {
+                if depth == 0 {
+                    root_is_mapping =
+                        matches!(event, Event::MappingStart(..));
+                } else if depth == 1 {
+                    expect_key = !expect_key;
+                }
+                depth += 1;
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                depth = depth.saturating_sub(1);
+            }
+            Event::Scalar(key, ..) if depth == 1 && root_is_mapping => {
+                if expect_key
+                    && !seen.insert(key.clone())
+                    && !duplicates.contains(&key)
+                {
+                    duplicates.push(key);
+                }
+                expect_key = !expect_key;
+            }
+            Event::Alias(_) if depth == 1 => expect_key = !expect_key,
+            _ => {}
+        }
+    }
+    duplicates
 }
 
-fn flatten_yaml_recursive(
+/// A YAML mapping that keeps the last value of a duplicate key, instead of
+/// rejecting it as `serde_yml::Mapping` does.
+///
+/// Only the keys of this mapping are affected; nested mappings are
+/// deserialized as usual.
+struct LastValueMapping(serde_yml::Mapping);
+
+impl<'de> serde::Deserialize<'de> for LastValueMapping {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct MappingVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for MappingVisitor {
+            type Value = LastValueMapping;
+
+            fn expecting(
+                &self,
+                formatter: &mut std::fmt::Formatter<'_>,
+            ) -> std::fmt::Result {
+                formatter.write_str("a YAML mapping")
+            }
+
+            fn visit_map<A>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                let mut mapping = serde_yml::Mapping::new();
+                while let Some((key, value)) = map
+                    .next_entry::<serde_yml::Value, serde_yml::Value>(
+                    )?
+                {
+                    mapping.insert(key, value);
+                }
+                Ok(LastValueMapping(mapping))
+            }
+        }
+
+        deserializer.deserialize_map(MappingVisitor)
+    }
+}
+
+/// Collects a warning for each boolean value in a YAML mapping.
+fn collect_yaml_bool_warnings(
     value: &serde_yml::Value,
-    prefix: String,
-    map: &mut HashMap<String, String>,
+    prefix: &str,
+    warnings: &mut Vec<String>,
 ) {
     match value {
         serde_yml::Value::Mapping(m) => {
             for (k, v) in m {
-                let new_prefix = if prefix.is_empty() {
-                    k.as_str().unwrap_or_default().to_string()
+                let key = k.as_str().unwrap_or_default();
+                let key = if prefix.is_empty() {
+                    key.to_string()
                 } else {
-                    format!(
-                        "{}.{}",
-                        prefix,
-                        k.as_str().unwrap_or_default()
-                    )
+                    format!("{}.{}", prefix, key)
                 };
-                flatten_yaml_recursive(v, new_prefix, map);
+                collect_yaml_bool_warnings(v, &key, warnings);
             }
         }
-        serde_yml::Value::Sequence(seq) => {
-            let inline_list = seq
-                .iter()
-                .filter_map(|item| item.as_str().map(|s| s.to_string()))
-                .collect::<Vec<String>>()
-                .join(", ");
-            map.insert(prefix, format!("[{}]", inline_list));
+        serde_yml::Value::Tagged(tagged) => {
+            collect_yaml_bool_warnings(&tagged.value, prefix, warnings);
         }
-        _ => {
-            map.insert(
-                prefix,
-                value.as_str().unwrap_or_default().to_string(),
-            );
+        serde_yml::Value::Bool(_) => {
+            warnings.push(format!(
+                "{}: boolean value coerced to string",
+                prefix
+            ));
         }
+        _ => {}
     }
 }
 
-/// Extracts TOML metadata from the content.
+/// Extracts metadata from the content string using the given options.
 ///
 /// # Arguments
 ///
-/// * `content` - A string slice containing the content to extract TOML metadata from.
+/// * `content` - A string slice containing the content to extract metadata from.
+/// * `options` - The `ExtractOptions` controlling extraction.
 ///
 /// # Returns
 ///
-/// An `Option<Metadata>` containing the extracted metadata if successful, or `None` if extraction fails.
-fn extract_toml_metadata(content: &str) -> Option<Metadata> {
-    let re = Regex::new(r"(?s)^\s*\+\+\+\s*(.*?)\s*\+\+\+").ok()?;
-    let captures = re.captures(content)?;
-    let toml_str = captures.get(1)?.as_str().trim();
+/// A `Result` containing the extracted `Metadata` if successful, or a `MetadataError` if extraction fails.
+///
+/// # Errors
+///
+/// Returns a `MetadataError::ExtractionError` if no valid front matter is
+/// found or it is larger than `options.max_front_matter_bytes`, a
+/// `MetadataError::ProcessingError` if the front matter is nested deeper
+/// than `options.max_depth`,
+/// or a `MetadataError::ValidationError` if any
+/// delimiter is empty.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::metadata::{
+///     extract_metadata_with_options, Delimiters, ExtractOptions,
+/// };
+///
+/// let content = "===\ntitle: Custom\n===\nBody";
+/// let options = ExtractOptions {
+///     yaml_delimiters: Delimiters::new("===", "==="),
+///     ..ExtractOptions::default()
+/// };
+/// let metadata = extract_metadata_with_options(content, &options).unwrap();
+/// assert_eq!(metadata.get("title").unwrap(), "Custom");
+/// ```
+pub fn extract_metadata_with_options(
+    content: &str,
+    options: &ExtractOptions,
+) -> Result<Metadata, MetadataError> {
+    options.yaml_delimiters.validate("yaml_delimiters")?;
+    options.toml_delimiters.validate("toml_delimiters")?;
+    options.json_delimiters.validate("json_delimiters")?;
 
-    let toml_value: TomlValue = toml::from_str(toml_str).ok()?;
+    let content = normalize_content(content);
+    let content = content.as_ref();
 
-    let mut metadata = HashMap::new();
-    flatten_toml(&toml_value, &mut metadata, String::new());
+    let mut metadata = match extract_yaml_metadata(content, options)? {
+        Some(metadata) => metadata,
+        None => match extract_toml_metadata(content, options)? {
+            Some(metadata) => metadata,
+            None => match extract_json_metadata(content, options)? {
+                Some(metadata) => metadata,
+                None => {
+                    let block = rst_field_list_block(content)
+                        .ok_or_else(|| {
+                            MetadataError::ExtractionError {
+                                message: "No valid front matter found."
+                                    .to_string(),
+                            }
+                        })?;
+                    check_front_matter_size(block, options)?;
+                    parse_rst_field_list(block)
+                }
+            },
+        },
+    };
 
-    Some(Metadata::new(metadata))
+    if options.normalize_keys {
+        metadata.normalize_keys();
+    }
+    Ok(metadata)
 }
 
-fn flatten_toml(
-    value: &TomlValue,
-    map: &mut HashMap<String, String>,
-    prefix: String,
-) {
-    match value {
-        TomlValue::Table(table) => {
-            for (k, v) in table {
-                let new_prefix = if prefix.is_empty() {
-                    k.to_string()
-                } else {
-                    format!("{}.{}", prefix, k)
-                };
-                flatten_toml(v, map, new_prefix);
-            }
+/// Detects the format of the front matter at the start of the content.
+///
+/// Only the opening delimiter is checked, using the default delimiters of
+/// `ExtractOptions`, so this is much cheaper than `extract_metadata` for
+/// filtering files. The front matter itself is not validated.
+///
+/// # Arguments
+///
+/// * `content` - A string slice containing the content to check.
+///
+/// # Returns
+///
+/// The detected `FrontMatterFormat`, or `None` if the content does not
+/// start with a front matter delimiter.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::metadata::{has_front_matter, FrontMatterFormat};
+///
+/// assert_eq!(
+///     has_front_matter("---\ntitle: Hello\n---\n"),
+///     Some(FrontMatterFormat::Yaml)
+/// );
+/// assert_eq!(has_front_matter("# Just a heading"), None);
+/// ```
+pub fn has_front_matter(content: &str) -> Option<FrontMatterFormat> {
+    let content = content.strip_prefix('\u{FEFF}').unwrap_or(content);
+    let content = content.trim_start();
+    let first_line =
+        content.lines().next().unwrap_or_default().trim_end();
+
+    if first_line == "---" {
+        Some(FrontMatterFormat::Yaml)
+    } else if first_line == "+++" || first_line == TOML_FENCE_OPEN {
+        Some(FrontMatterFormat::Toml)
+    } else if content.starts_with('{') {
+        Some(FrontMatterFormat::Json)
+    } else {
+        None
+    }
+}
+
+/// Returns the body of the content, after its front matter.
+///
+/// The front matter is detected as by `has_front_matter`. YAML and TOML
+/// blocks end at the next line containing only the closing delimiter, and
+/// JSON front matter ends at the brace closing the opening one. Content
+/// without front matter, or with an unclosed block, is returned unchanged.
+///
+/// # Arguments
+///
+/// * `content` - A string slice containing the content.
+///
+/// # Returns
+///
+/// The body of the content.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::metadata::strip_front_matter;
+///
+/// assert_eq!(strip_front_matter("---\ntitle: Hi\n---\nBody"), "Body");
+/// assert_eq!(strip_front_matter("Just a body"), "Just a body");
+/// ```
+pub fn strip_front_matter(content: &str) -> &str {
+    let end = match front_matter_span(content) {
+        Some((_, span)) => span.end,
+        None => return content,
+    };
+    let rest = content
+        .get(end..)
+        .unwrap_or_default()
+        .trim_start_matches([' ', '\t']);
+    rest.strip_prefix("\r\n")
+        .or_else(|| rest.strip_prefix('\n'))
+        .unwrap_or(rest)
+}
+
+/// Finds the byte range of the front matter in the content.
+///
+/// The front matter is detected as by `has_front_matter`, and ends as
+/// described for `strip_front_matter`. The range starts at the opening
+/// delimiter, after any byte order mark and leading whitespace, and ends
+/// just past the closing delimiter, before the line ending that follows
+/// it. Replacing `content[range]` therefore replaces the whole fenced
+/// block while keeping the body intact.
+///
+/// # Arguments
+///
+/// * `content` - A string slice containing the content.
+///
+/// # Returns
+///
+/// The format and byte range of the front matter, or `None` if no
+/// front matter is found or the block is not closed.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::metadata::{front_matter_span, FrontMatterFormat};
+///
+/// let content = "---\ntitle: Hi\n---\nBody";
+/// let (format, span) = front_matter_span(content).unwrap();
+/// assert_eq!(format, FrontMatterFormat::Yaml);
+/// assert_eq!(&content[span], "---\ntitle: Hi\n---");
+/// ```
+pub fn front_matter_span(
+    content: &str,
+) -> Option<(FrontMatterFormat, Range<usize>)> {
+    let start = content.strip_prefix('\u{FEFF}').unwrap_or(content);
+    let start = start.trim_start();
+    let offset = content.len() - start.len();
+
+    let format = has_front_matter(start)?;
+    let delimiter = match format {
+        FrontMatterFormat::Yaml => "---",
+        FrontMatterFormat::Toml
+            if start.starts_with(TOML_FENCE_OPEN) =>
+        {
+            "---"
         }
-        TomlValue::Array(arr) => {
-            let inline_list = arr
-                .iter()
-                .map(|v| {
-                    // Remove double quotes for string elements
-                    match v {
-                        TomlValue::String(s) => s.clone(),
-                        _ => v.to_string(),
-                    }
-                })
-                .collect::<Vec<String>>()
-                .join(", ");
-            map.insert(prefix, format!("[{}]", inline_list));
+        FrontMatterFormat::Toml => "+++",
+        FrontMatterFormat::Json => {
+            let end = json_object_end(start)?;
+            return Some((format, offset..offset + end));
         }
-        TomlValue::String(s) => {
-            map.insert(prefix, s.clone());
+    };
+
+    let mut line_start = 0;
+    for (index, line) in start.split_inclusive('\n').enumerate() {
+        if index > 0 && line.trim_end() == delimiter {
+            let end = line_start + delimiter.len();
+            return Some((format, offset..offset + end));
         }
-        TomlValue::Datetime(dt) => {
-            map.insert(prefix, dt.to_string());
+        line_start += line.len();
+    }
+    None
+}
+
+/// Returns the byte offset just past the brace closing the JSON object
+/// that `content` starts with.
+fn json_object_end(content: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (index, c) in content.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
         }
-        _ => {
-            map.insert(prefix, value.to_string());
+        match c {
+            '"' => in_string = true,
+            '{' => depth += 1,
+            '}' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(index + 1);
+                }
+            }
+            _ => {}
         }
     }
+    None
 }
 
-/// Extracts JSON metadata from the content.
+/// Extracts every YAML front matter block from the content.
+///
+/// A block starts with a `---` line and ends at the next `---` line, both
+/// of which must contain only the delimiter and optional whitespace. Only
+/// blocks whose contents parse as a YAML mapping are returned, so a `---`
+/// thematic break in the body does not produce a result.
 ///
 /// # Arguments
 ///
-/// * `content` - A string slice containing the content to extract JSON metadata from.
+/// * `content` - A string slice containing the content to extract metadata from.
 ///
 /// # Returns
 ///
-/// An `Option<Metadata>` containing the extracted metadata if successful, or `None` if extraction fails.
-fn extract_json_metadata(content: &str) -> Option<Metadata> {
-    let re = Regex::new(r"(?s)^\s*\{\s*(.*?)\s*\}").ok()?;
-    let captures = re.captures(content)?;
-    let json_str = format!("{{{}}}", captures.get(1)?.as_str().trim());
-
-    let json_value: JsonValue = serde_json::from_str(&json_str).ok()?;
-    let json_object = json_value.as_object()?;
+/// A vector of `(offset, Metadata)` pairs in document order, where `offset`
+/// is the byte offset of the opening delimiter in `content`.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::metadata::extract_all_metadata;
+///
+/// let content = "---\ntitle: One\n---\nBody\n---\ntitle: Two\n---\n";
+/// let blocks = extract_all_metadata(content);
+/// assert_eq!(blocks.len(), 2);
+/// assert_eq!(blocks[1].0, 24);
+/// ```
+pub fn extract_all_metadata(content: &str) -> Vec<(usize, Metadata)> {
+    let mut fences = Vec::new();
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start_matches('\u{FEFF}').trim();
+        if trimmed == "---" {
+            let indent = line.len() - line.trim_start().len();
+            fences.push((offset + indent, offset + line.len()));
+        }
+        offset += line.len();
+    }
 
-    let metadata: HashMap<String, String> = json_object
-        .iter()
-        .filter_map(|(k, v)| {
-            v.as_str().map(|s| (k.clone(), s.to_string()))
-        })
-        .collect();
+    let mut blocks = Vec::new();
+    let mut index = 0;
+    while let (Some(&(open_start, open_end)), Some(&(close_start, _))) =
+        (fences.get(index), fences.get(index + 1))
+    {
+        let block =
+            content.get(open_end..close_start).unwrap_or_default();
 
-    Some(Metadata::new(metadata))
+        let parsed = serde_yml::from_str::<serde_yml::Value>(block)
+            .ok()
+            .filter(serde_yml::Value::is_mapping)
+            .and_then(|value| {
+                flatten_yaml(&value, DEFAULT_MAX_DEPTH).ok()
+            });
+        match parsed {
+            Some(metadata) => {
+                blocks.push((open_start, Metadata::new(metadata)));
+                index += 2;
+            }
+            // The closing fence may open the next block instead.
+            None => index += 1,
+        }
+    }
+    blocks
 }
 
-/// Processes the extracted metadata.
+/// Extracts metadata from the front matter starting at a byte offset.
 ///
-/// This function standardizes dates, ensures required fields are present, and generates derived fields.
+/// Only the content from `start` onward is considered, so the front
+/// matter must begin at, or after leading whitespace following, `start`.
+/// This pairs with the offsets returned by `extract_all_metadata` to
+/// re-read a single block of a concatenated document.
 ///
 /// # Arguments
 ///
-/// * `metadata` - A reference to the `Metadata` instance to process.
+/// * `content` - A string slice containing the content to extract metadata from.
+/// * `start` - The byte offset at which to start looking for front matter.
 ///
 /// # Returns
 ///
-/// A `Result` containing the processed `Metadata` if successful, or a `MetadataError` if processing fails.
+/// A `Result` containing the extracted `Metadata` if successful, or a `MetadataError` if extraction fails.
 ///
 /// # Errors
 ///
-/// Returns a `MetadataError` if date standardization fails or if required fields are missing.
-pub fn process_metadata(
-    metadata: &Metadata,
+/// Returns a `MetadataError::ExtractionError` if `start` is past the end
+/// of `content` or not on a character boundary, and otherwise the same
+/// errors as `extract_metadata`.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::metadata::extract_metadata_at;
+///
+/// let content = "---\ntitle: One\n---\nBody\n---\ntitle: Two\n---\n";
+/// let metadata = extract_metadata_at(content, 24).unwrap();
+/// assert_eq!(metadata.get("title").unwrap(), "Two");
+/// ```
+pub fn extract_metadata_at(
+    content: &str,
+    start: usize,
 ) -> Result<Metadata, MetadataError> {
-    let mut processed = metadata.clone();
-
-    // Convert dates to a standard format
-    if let Some(date) = processed.get("date").cloned() {
-        let standardized_date = standardize_date(&date)?;
-        processed.insert("date".to_string(), standardized_date);
-    }
+    let rest =
+        content
+            .get(start..)
+            .ok_or_else(|| MetadataError::ExtractionError {
+                message: format!(
+                    "Offset {} is out of bounds or not on a character boundary",
+                    start
+                ),
+            })?;
+    extract_metadata(rest)
+}
 
-    // Ensure required fields are present
-    ensure_required_fields(&processed)?;
+/// Parses a raw metadata string in the given format.
+///
+/// Unlike `extract_metadata`, the input is not expected to be wrapped in
+/// front matter delimiters: the whole string is parsed as `format` and
+/// flattened in the same way as extracted front matter. This suits
+/// callers that have already split the front matter from the body. As
+/// with an empty fenced block, empty YAML input yields empty `Metadata`.
+///
+/// # Arguments
+///
+/// * `input` - A string slice containing the raw YAML, TOML, or JSON.
+/// * `format` - The `FrontMatterFormat` of the input.
+///
+/// # Returns
+///
+/// A `Result` containing the parsed `Metadata` if successful, or a `MetadataError` if parsing fails.
+///
+/// # Errors
+///
+/// Returns a `MetadataError::YamlError`, `MetadataError::TomlError`, or
+/// `MetadataError::JsonError` if the input is not valid in the given
+/// format, a `MetadataError::ExtractionError` if the YAML or JSON input is
+/// not a mapping, or a `MetadataError::ProcessingError` if it is nested
+/// deeper than `DEFAULT_MAX_DEPTH`.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::metadata::{parse_metadata_string, FrontMatterFormat};
+///
+/// let metadata =
+///     parse_metadata_string("title: Raw\n", FrontMatterFormat::Yaml).unwrap();
+/// assert_eq!(metadata.get("title").unwrap(), "Raw");
+/// ```
+pub fn parse_metadata_string(
+    input: &str,
+    format: FrontMatterFormat,
+) -> Result<Metadata, MetadataError> {
+    let input = normalize_content(input);
+    let input = input.as_ref();
 
-    // Generate derived fields
-    generate_derived_fields(&mut processed);
+    let metadata = match format {
+        FrontMatterFormat::Yaml => {
+            let value: serde_yml::Value = serde_yml::from_str(input)?;
+            if value.is_null() {
+                return Ok(Metadata::default());
+            }
+            if !value.is_mapping() {
+                return Err(MetadataError::new_extraction_error(
+                    "YAML metadata must be a mapping",
+                ));
+            }
+            flatten_yaml(&value, DEFAULT_MAX_DEPTH)?
+        }
+        FrontMatterFormat::Toml => {
+            let value: TomlValue = toml::from_str(input)?;
+            let mut map = HashMap::new();
+            flatten_toml(
+                &value,
+                &mut map,
+                String::new(),
+                0,
+                DEFAULT_MAX_DEPTH,
+            )?;
+            map
+        }
+        FrontMatterFormat::Json => {
+            let value: JsonValue = serde_json::from_str(input)?;
+            if !value.is_object() {
+                return Err(MetadataError::new_extraction_error(
+                    "JSON metadata must be an object",
+                ));
+            }
+            flatten_json(&value, DEFAULT_MAX_DEPTH)?
+        }
+    };
 
-    Ok(processed)
+    Ok(Metadata::new(metadata))
 }
 
-/// Standardizes the date format.
+/// Parses an already-split front matter block in the given format.
 ///
-/// This function attempts to parse various date formats and convert them to the YYYY-MM-DD format.
+/// This is `parse_metadata_string` under the name used for front matter:
+/// `block` is the text between the delimiters, without the delimiters.
 ///
 /// # Arguments
 ///
-/// * `date` - A string slice containing the date to standardize.
+/// * `block` - A string slice containing the raw front matter block.
+/// * `format` - The `FrontMatterFormat` of the block.
 ///
 /// # Returns
 ///
-/// A `Result` containing the standardized date string if successful, or a `MetadataError` if parsing fails.
+/// A `Result` containing the parsed `Metadata` if successful, or a `MetadataError` if parsing fails.
 ///
 /// # Errors
 ///
-/// Returns a `MetadataError::DateParseError` if the date cannot be parsed or is invalid.
-fn standardize_date(date: &str) -> Result<String, MetadataError> {
-    // Handle edge cases with empty or too-short dates
-    if date.trim().is_empty() {
-        return Err(MetadataError::DateParseError(
-            "Date string is empty.".to_string(),
-        ));
+/// Returns the same errors as `parse_metadata_string`.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::metadata::{parse_front_matter, FrontMatterFormat};
+///
+/// let metadata =
+///     parse_front_matter("title = \"Raw\"\n", FrontMatterFormat::Toml).unwrap();
+/// assert_eq!(metadata.get("title").unwrap(), "Raw");
+/// ```
+pub fn parse_front_matter(
+    block: &str,
+    format: FrontMatterFormat,
+) -> Result<Metadata, MetadataError> {
+    parse_metadata_string(block, format)
+}
+
+/// Normalizes content before front matter extraction.
+///
+/// A leading UTF-8 byte order mark (`U+FEFF`) is removed, since it is not
+/// whitespace and would otherwise prevent the opening delimiter from
+/// matching, and Windows (`\r\n`) line endings are converted to `\n`.
+/// Leading blank lines are left in place, as the delimiter patterns
+/// already skip them.
+///
+/// # Arguments
+///
+/// * `content` - A string slice containing the content to normalize.
+///
+/// # Returns
+///
+/// The normalized content, borrowed when no changes were needed.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::metadata::normalize_content;
+///
+/// let content = "\u{FEFF}---\r\ntitle: Hello\r\n---\r\n";
+/// assert_eq!(normalize_content(content), "---\ntitle: Hello\n---\n");
+/// ```
+pub fn normalize_content(content: &str) -> Cow<'_, str> {
+    let content = content.strip_prefix('\u{FEFF}').unwrap_or(content);
+    if content.contains("\r\n") {
+        Cow::Owned(content.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(content)
     }
+}
 
-    if date.len() < 8 {
-        return Err(MetadataError::DateParseError(
-            "Date string is too short.".to_string(),
-        ));
+/// Returns the error reported when front matter exceeds the maximum depth.
+fn max_depth_error(max_depth: usize) -> MetadataError {
+    MetadataError::ProcessingError {
+        message: format!(
+            "Front matter exceeds the maximum nesting depth of {}",
+            max_depth
+        ),
     }
+}
 
-    // Check if the date is in the DD/MM/YYYY format and reformat to YYYY-MM-DD
-    let date = if date.contains('/') && date.len() == 10 {
-        let parts: Vec<&str> = date.split('/').collect();
-        if parts.len() == 3
-            && parts[0].len() == 2
-            && parts[1].len() == 2
-            && parts[2].len() == 4
-        {
-            format!("{}-{}-{}", parts[2], parts[1], parts[0]) // Reformat to YYYY-MM-DD
-        } else {
-            return Err(MetadataError::DateParseError(
-                "Invalid DD/MM/YYYY date format.".to_string(),
-            ));
+/// Checks a captured front matter block against the configured size limit.
+fn check_front_matter_size(
+    block: &str,
+    options: &ExtractOptions,
+) -> Result<(), MetadataError> {
+    match options.max_front_matter_bytes {
+        Some(limit) if block.len() > limit => {
+            Err(MetadataError::ExtractionError {
+                message: format!(
+                    "Front matter of {} bytes exceeds the maximum size of {} bytes",
+                    block.len(),
+                    limit
+                ),
+            })
         }
-    } else {
-        date.to_string()
-    };
+        _ => Ok(()),
+    }
+}
+
+/// Extracts YAML metadata from the content.
+///
+/// # Arguments
+///
+/// * `content` - A string slice containing the content to extract YAML metadata from.
+/// * `options` - The `ExtractOptions` controlling extraction.
+///
+/// # Returns
+///
+/// `Ok(Some(Metadata))` if YAML front matter was extracted, `Ok(None)` if none was found,
+/// or a `MetadataError` if the front matter is too large or nested too deeply.
+fn extract_yaml_metadata(
+    content: &str,
+    options: &ExtractOptions,
+) -> Result<Option<Metadata>, MetadataError> {
+    match yaml_block(content, options) {
+        Some(block) => parse_yaml_block(block, options, false),
+        None => Ok(None),
+    }
+}
+
+/// Parses a YAML front matter block, without its delimiters.
+///
+/// # Arguments
+///
+/// * `yaml_str` - A string slice containing the YAML block.
+/// * `options` - The `ExtractOptions` controlling extraction.
+/// * `keep_last_duplicate` - Whether a duplicate top-level key keeps its
+///   last value, instead of making the block invalid.
+///
+/// # Returns
+///
+/// `Ok(Some(Metadata))` if the block was parsed, `Ok(None)` if it is not
+/// valid YAML, or a `MetadataError` if it is too large or nested too
+/// deeply.
+fn parse_yaml_block(
+    yaml_str: &str,
+    options: &ExtractOptions,
+    keep_last_duplicate: bool,
+) -> Result<Option<Metadata>, MetadataError> {
+    check_front_matter_size(yaml_str, options)?;
+
+    let parsed = if keep_last_duplicate {
+        serde_yml::from_str::<LastValueMapping>(yaml_str)
+            .map(|mapping| serde_yml::Value::Mapping(mapping.0))
+    } else {
+        serde_yml::from_str(yaml_str)
+    };
+    let yaml_value = match parsed {
+        Ok(value) => value,
+        Err(_) => return Ok(None),
+    };
+    // An empty block, or one with only comments, is empty metadata.
+    if yaml_value.is_null() {
+        return Ok(Some(Metadata::new(HashMap::new())));
+    }
+
+    let mut metadata = flatten_yaml(&yaml_value, options.max_depth)?;
+    if !options.normalize_scalars {
+        for (key, raw) in raw_yaml_scalars(yaml_str) {
+            if let Some(value) = metadata.get_mut(&key) {
+                *value = raw;
+            }
+        }
+    }
+
+    Ok(Some(Metadata::new(metadata)))
+}
+
+/// Finds the YAML front matter block, without its delimiters.
+///
+/// An empty block, with the closing delimiter directly after the opening
+/// one, is returned as an empty string.
+fn yaml_block<'a>(
+    content: &'a str,
+    options: &ExtractOptions,
+) -> Option<&'a str> {
+    let pattern = format!(
+        r"(?s)^\s*{}\s*\n(?:(.*?)\r?\n)?\s*{}\s*",
+        regex::escape(&options.yaml_delimiters.open),
+        regex::escape(&options.yaml_delimiters.close)
+    );
+    let re = Regex::new(&pattern).ok()?;
+    re.captures(content)
+        .map(|c| c.get(1).map_or("", |m| m.as_str().trim()))
+}
+
+fn flatten_yaml(
+    value: &serde_yml::Value,
+    max_depth: usize,
+) -> Result<HashMap<String, String>, MetadataError> {
+    // Aliases are resolved by the parser, but `<<` merge keys are not.
+    // An invalid merge, such as `<<: 1`, is flattened as a plain key.
+    let mut merged = value.clone();
+    let value = if merged.apply_merge().is_ok() {
+        &merged
+    } else {
+        value
+    };
+    let mut map = HashMap::new();
+    flatten_yaml_recursive(
+        value,
+        String::new(),
+        &mut map,
+        0,
+        max_depth,
+    )?;
+    Ok(map)
+}
+
+fn flatten_yaml_recursive(
+    value: &serde_yml::Value,
+    prefix: String,
+    map: &mut HashMap<String, String>,
+    depth: usize,
+    max_depth: usize,
+) -> Result<(), MetadataError> {
+    match value {
+        serde_yml::Value::Mapping(m) => {
+            if depth >= max_depth {
+                return Err(max_depth_error(max_depth));
+            }
+            for (k, v) in m {
+                let new_prefix = if prefix.is_empty() {
+                    k.as_str().unwrap_or_default().to_string()
+                } else {
+                    format!(
+                        "{}.{}",
+                        prefix,
+                        k.as_str().unwrap_or_default()
+                    )
+                };
+                flatten_yaml_recursive(
+                    v,
+                    new_prefix,
+                    map,
+                    depth + 1,
+                    max_depth,
+                )?;
+            }
+        }
+        serde_yml::Value::Sequence(seq) => {
+            // Mappings inside a sequence are flattened to indexed keys
+            // such as `authors.0.name`.
+            for (index, item) in seq.iter().enumerate() {
+                if item.is_mapping() {
+                    flatten_yaml_recursive(
+                        item,
+                        format!("{}.{}", prefix, index),
+                        map,
+                        depth + 1,
+                        max_depth,
+                    )?;
+                }
+            }
+            if !seq.is_empty()
+                && seq.iter().all(|item| item.is_mapping())
+            {
+                return Ok(());
+            }
+
+            let items = seq
+                .iter()
+                .filter_map(yaml_scalar_to_string)
+                .collect::<Vec<String>>();
+            let inline_list =
+                format_inline_list(items.iter().map(String::as_str));
+            map.insert(prefix, inline_list);
+        }
+        serde_yml::Value::Tagged(tagged) => {
+            flatten_yaml_recursive(
+                &tagged.value,
+                prefix,
+                map,
+                depth,
+                max_depth,
+            )?;
+        }
+        _ => {
+            map.insert(
+                prefix,
+                yaml_scalar_to_string(value).unwrap_or_default(),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Converts a YAML scalar to its string representation.
+///
+/// Strings, numbers, and booleans are converted, and tagged values such
+/// as `!!timestamp 2023-05-20` are converted from their inner value.
+/// Nulls, sequences, and mappings yield `None`.
+fn yaml_scalar_to_string(value: &serde_yml::Value) -> Option<String> {
+    match value {
+        serde_yml::Value::String(s) => Some(s.clone()),
+        serde_yml::Value::Number(n) => Some(n.to_string()),
+        serde_yml::Value::Bool(b) => Some(b.to_string()),
+        serde_yml::Value::Tagged(tagged) => {
+            yaml_scalar_to_string(&tagged.value)
+        }
+        _ => None,
+    }
+}
+
+/// The key state of a mapping read by `raw_yaml_scalars`.
+enum RawKey {
+    /// The next scalar is a key.
+    Expected,
+    /// The next value belongs to this key.
+    Key(String),
+    /// The next value belongs to a key that cannot be flattened.
+    Ignored,
+}
+
+/// A mapping or sequence being read by `raw_yaml_scalars`.
+enum RawFrame {
+    Mapping {
+        prefix: String,
+        key: RawKey,
+    },
+    Sequence {
+        prefix: String,
+        index: usize,
+        mappings: usize,
+        items: Vec<String>,
+        complete: bool,
+    },
+}
+
+/// Collects the original text of the scalars of a YAML block.
+///
+/// The keys follow the flattening rules of `flatten_yaml`, so that the
+/// result can be overlaid on its output to undo the normalization of
+/// booleans and numbers. Null scalars, aliases, and complex keys are left
+/// out, as are lists containing aliases.
+fn raw_yaml_scalars(block: &str) -> HashMap<String, String> {
+    let mut parser = Parser::new_from_str(block);
+    let mut raw = HashMap::new();
+    let mut stack: Vec<RawFrame> = Vec::new();
+    // The nesting depth of a collection that is being skipped.
+    let mut skip = 0usize;
+
+    while let Ok((event, _)) = parser.next_token() {
+        match event {
+            Event::StreamEnd => break,
+            Event::MappingStart(..) | Event::SequenceStart(..)
+                if skip > 0 =>
+            {
+                skip += 1;
+            }
+            Event::MappingEnd | Event::SequenceEnd if skip > 0 => {
+                skip -= 1;
+                if skip == 0 {
+                    match stack.last_mut() {
+                        Some(RawFrame::Mapping { key, .. })
+                            if matches!(key, RawKey::Expected) =>
+                        {
+                            *key = RawKey::Ignored;
+                        }
+                        _ => finish_raw_value(&mut stack, false),
+                    }
+                }
+            }
+            _ if skip > 0 => {}
+            Event::MappingStart(..) => {
+                match raw_child_prefix(&stack, true) {
+                    Some(prefix) => stack.push(RawFrame::Mapping {
+                        prefix,
+                        key: RawKey::Expected,
+                    }),
+                    None => skip = 1,
+                }
+            }
+            Event::SequenceStart(..) => {
+                match raw_child_prefix(&stack, false) {
+                    Some(prefix) => stack.push(RawFrame::Sequence {
+                        prefix,
+                        index: 0,
+                        mappings: 0,
+                        items: Vec::new(),
+                        complete: true,
+                    }),
+                    None => skip = 1,
+                }
+            }
+            Event::MappingEnd => {
+                stack.pop();
+                finish_raw_value(&mut stack, true);
+            }
+            Event::SequenceEnd => {
+                if let Some(RawFrame::Sequence {
+                    prefix,
+                    index,
+                    mappings,
+                    items,
+                    complete,
+                }) = stack.pop()
+                {
+                    if complete && (index == 0 || mappings < index) {
+                        raw.insert(
+                            prefix,
+                            format_inline_list(
+                                items.iter().map(String::as_str),
+                            ),
+                        );
+                    }
+                }
+                finish_raw_value(&mut stack, false);
+            }
+            Event::Scalar(value, style, _, _) => {
+                let is_null = is_yaml_null(&value, style);
+                match stack.last_mut() {
+                    Some(RawFrame::Mapping { key, .. })
+                        if matches!(key, RawKey::Expected) =>
+                    {
+                        *key = RawKey::Key(value);
+                        continue;
+                    }
+                    Some(RawFrame::Mapping {
+                        prefix,
+                        key: RawKey::Key(key),
+                    }) if !is_null => {
+                        raw.insert(join_key(prefix, key), value);
+                    }
+                    Some(RawFrame::Sequence { items, .. })
+                        if !is_null =>
+                    {
+                        items.push(value);
+                    }
+                    _ => {}
+                }
+                finish_raw_value(&mut stack, false);
+            }
+            Event::Alias(_) => {
+                match stack.last_mut() {
+                    Some(RawFrame::Mapping { key, .. })
+                        if matches!(key, RawKey::Expected) =>
+                    {
+                        *key = RawKey::Ignored;
+                        continue;
+                    }
+                    Some(RawFrame::Sequence { complete, .. }) => {
+                        *complete = false;
+                    }
+                    _ => {}
+                }
+                finish_raw_value(&mut stack, false);
+            }
+            _ => {}
+        }
+    }
+    raw
+}
+
+/// Returns the flattened key of a collection starting in the top frame,
+/// or `None` if the collection is not flattened by `flatten_yaml`.
+fn raw_child_prefix(
+    stack: &[RawFrame],
+    is_mapping: bool,
+) -> Option<String> {
+    match stack.last() {
+        None => Some(String::new()),
+        Some(RawFrame::Mapping {
+            prefix,
+            key: RawKey::Key(key),
+        }) => Some(join_key(prefix, key)),
+        Some(RawFrame::Sequence { prefix, index, .. })
+            if is_mapping =>
+        {
+            Some(format!("{}.{}", prefix, index))
+        }
+        _ => None,
+    }
+}
+
+/// Moves the top frame past a value that has been read.
+fn finish_raw_value(stack: &mut [RawFrame], is_mapping: bool) {
+    match stack.last_mut() {
+        Some(RawFrame::Mapping { key, .. }) => *key = RawKey::Expected,
+        Some(RawFrame::Sequence {
+            index, mappings, ..
+        }) => {
+            *index += 1;
+            if is_mapping {
+                *mappings += 1;
+            }
+        }
+        None => {}
+    }
+}
+
+/// Joins a key to its parent key with a dot.
+fn join_key(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+/// Checks whether a YAML scalar is a null.
+fn is_yaml_null(value: &str, style: TScalarStyle) -> bool {
+    style == TScalarStyle::Plain
+        && matches!(value, "" | "~" | "null" | "Null" | "NULL")
+}
+
+/// Formats list elements as an inline `[a, b]` list.
+///
+/// Elements containing a comma or a double quote are wrapped in double
+/// quotes, with inner quotes and backslashes escaped, so that the element
+/// boundaries can be recovered by `parse_inline_list`.
+fn format_inline_list<'a>(
+    items: impl Iterator<Item = &'a str>,
+) -> String {
+    let items = items
+        .map(|item| {
+            if item.contains(',') || item.contains('"') {
+                format!(
+                    "\"{}\"",
+                    item.replace('\\', "\\\\").replace('"', "\\\"")
+                )
+            } else {
+                item.to_string()
+            }
+        })
+        .collect::<Vec<String>>();
+    format!("[{}]", items.join(", "))
+}
+
+/// Converts an inline list such as `[a, b, c]` to `a, b, c`.
+///
+/// A value that is not wrapped in brackets is returned unchanged.
+pub(crate) fn inline_list_to_comma_separated(value: &str) -> String {
+    parse_inline_list(value).join(", ")
+}
+
+/// Parses an inline list produced by `format_inline_list`.
+///
+/// A value that is not wrapped in brackets is returned as a single element.
+fn parse_inline_list(value: &str) -> Vec<String> {
+    let trimmed = value.trim();
+    let inner = match trimmed
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+    {
+        Some(inner) => inner,
+        None => return vec![value.to_string()],
+    };
+    if inner.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if quoted => {
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            }
+            '"' if quoted => quoted = false,
+            '"' if current.trim().is_empty() => {
+                current.clear();
+                quoted = true;
+            }
+            ',' if !quoted => {
+                items.push(current.trim().to_string());
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+    items.push(current.trim().to_string());
+    items
+}
+
+/// Inserts a value into nested TOML tables along a dotted key path.
+fn insert_toml_path(
+    table: &mut toml::Table,
+    key: &str,
+    path: &[&str],
+    value: TomlValue,
+) -> Result<(), MetadataError> {
+    let conflict = || {
+        toml_error(format!(
+            "Key `{}` conflicts with another key in the metadata",
+            key
+        ))
+    };
+    match path {
+        [] => Err(conflict()),
+        [last] => {
+            if table.contains_key(*last) {
+                return Err(conflict());
+            }
+            table.insert(last.to_string(), value);
+            Ok(())
+        }
+        [first, rest @ ..] => {
+            let child =
+                table.entry(first.to_string()).or_insert_with(|| {
+                    TomlValue::Table(toml::Table::new())
+                });
+            match child {
+                TomlValue::Table(child) => {
+                    insert_toml_path(child, key, rest, value)
+                }
+                _ => Err(conflict()),
+            }
+        }
+    }
+}
+
+/// Converts a metadata value to the TOML value written by
+/// `Metadata::to_toml_front_matter`.
+fn to_toml_value(value: String) -> TomlValue {
+    let items = parse_inline_list(&value);
+    let is_list = value.starts_with('[')
+        && items.len() != 1
+        && format_inline_list(items.iter().map(String::as_str))
+            == value;
+    if is_list {
+        return TomlValue::Array(
+            items.into_iter().map(TomlValue::String).collect(),
+        );
+    }
+
+    match value.as_str() {
+        "true" => return TomlValue::Boolean(true),
+        "false" => return TomlValue::Boolean(false),
+        _ => {}
+    }
+    if let Ok(integer) = value.parse::<i64>() {
+        if integer.to_string() == value {
+            return TomlValue::Integer(integer);
+        }
+    }
+    if let Ok(float) = value.parse::<f64>() {
+        if float.is_finite()
+            && value.contains('.')
+            && float.to_string() == value
+        {
+            return TomlValue::Float(float);
+        }
+    }
+    TomlValue::String(value)
+}
+
+/// Creates a `MetadataError::TomlError` with the given message.
+fn toml_error(message: String) -> MetadataError {
+    use serde::de::Error as _;
+    MetadataError::TomlError(toml::de::Error::custom(message))
+}
+
+/// Extracts TOML metadata from the content.
+///
+/// The block is delimited by `options.toml_delimiters`, `+++` by default.
+/// A block opened by a `---toml` line and closed by a `---` line is also
+/// recognized, as written by tools that share the YAML fence.
+///
+/// # Arguments
+///
+/// * `content` - A string slice containing the content to extract TOML metadata from.
+/// * `options` - The `ExtractOptions` controlling extraction.
+///
+/// # Returns
+///
+/// `Ok(Some(Metadata))` if TOML front matter was extracted, `Ok(None)` if none was found,
+/// or a `MetadataError` if the front matter is too large or nested too deeply.
+fn extract_toml_metadata(
+    content: &str,
+    options: &ExtractOptions,
+) -> Result<Option<Metadata>, MetadataError> {
+    let toml_str = match toml_block(
+        content,
+        &options.toml_delimiters.open,
+        &options.toml_delimiters.close,
+    )
+    .or_else(|| toml_block(content, TOML_FENCE_OPEN, "---"))
+    {
+        Some(block) => block,
+        None => return Ok(None),
+    };
+    check_front_matter_size(toml_str, options)?;
+
+    let toml_value: TomlValue = match toml::from_str(toml_str) {
+        Ok(value) => value,
+        Err(_) => return Ok(None),
+    };
+
+    let mut metadata = HashMap::new();
+    flatten_toml(
+        &toml_value,
+        &mut metadata,
+        String::new(),
+        0,
+        options.max_depth,
+    )?;
+
+    Ok(Some(Metadata::new(metadata)))
+}
+
+/// The opening delimiter of TOML front matter fenced as `---toml`/`---`.
+const TOML_FENCE_OPEN: &str = "---toml";
+
+/// Finds a TOML front matter block, without its delimiters.
+fn toml_block<'a>(
+    content: &'a str,
+    open: &str,
+    close: &str,
+) -> Option<&'a str> {
+    // The delimiters must be on their own lines, so that a `+++` inside
+    // a value or on a body line does not close the block early.
+    let pattern = format!(
+        r"(?s)^\s*{}[ \t]*\r?\n(?:(.*?)\r?\n)?[ \t]*{}[ \t]*(?:\r?\n|$)",
+        regex::escape(open),
+        regex::escape(close)
+    );
+    let re = Regex::new(&pattern).ok()?;
+    re.captures(content)
+        .map(|c| c.get(1).map_or("", |m| m.as_str().trim()))
+}
+
+fn flatten_toml(
+    value: &TomlValue,
+    map: &mut HashMap<String, String>,
+    prefix: String,
+    depth: usize,
+    max_depth: usize,
+) -> Result<(), MetadataError> {
+    match value {
+        TomlValue::Table(table) => {
+            if depth >= max_depth {
+                return Err(max_depth_error(max_depth));
+            }
+            for (k, v) in table {
+                let new_prefix = if prefix.is_empty() {
+                    k.to_string()
+                } else {
+                    format!("{}.{}", prefix, k)
+                };
+                flatten_toml(v, map, new_prefix, depth + 1, max_depth)?;
+            }
+        }
+        TomlValue::Array(arr) => {
+            // Tables inside an array are flattened to indexed keys
+            // such as `authors.0.name`.
+            for (index, item) in arr.iter().enumerate() {
+                if item.is_table() {
+                    flatten_toml(
+                        item,
+                        map,
+                        format!("{}.{}", prefix, index),
+                        depth + 1,
+                        max_depth,
+                    )?;
+                }
+            }
+            if !arr.is_empty() && arr.iter().all(|item| item.is_table())
+            {
+                return Ok(());
+            }
+
+            let items = arr
+                .iter()
+                .filter(|v| !v.is_table())
+                .map(|v| {
+                    // Remove double quotes for string elements
+                    match v {
+                        TomlValue::String(s) => s.clone(),
+                        _ => v.to_string(),
+                    }
+                })
+                .collect::<Vec<String>>();
+            let inline_list =
+                format_inline_list(items.iter().map(String::as_str));
+            map.insert(prefix, inline_list);
+        }
+        TomlValue::String(s) => {
+            map.insert(prefix, s.clone());
+        }
+        TomlValue::Datetime(dt) => {
+            map.insert(prefix, dt.to_string());
+        }
+        _ => {
+            map.insert(prefix, value.to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Extracts JSON metadata from the content.
+///
+/// # Arguments
+///
+/// * `content` - A string slice containing the content to extract JSON metadata from.
+/// * `options` - The `ExtractOptions` controlling extraction.
+///
+/// # Returns
+///
+/// `Ok(Some(Metadata))` if JSON front matter was extracted, `Ok(None)` if none was found,
+/// or a `MetadataError` if the front matter is too large.
+fn extract_json_metadata(
+    content: &str,
+    options: &ExtractOptions,
+) -> Result<Option<Metadata>, MetadataError> {
+    let json_body = match json_block(content, options) {
+        Some(block) => block,
+        None => return Ok(None),
+    };
+    check_front_matter_size(json_body, options)?;
+    let json_str = format!("{{{}}}", json_body);
+
+    let json_value = match parse_json_front_matter(&json_str) {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    if !json_value.is_object() {
+        return Ok(None);
+    }
+
+    let metadata = flatten_json(&json_value, options.max_depth)?;
+    Ok(Some(Metadata::new(metadata)))
+}
+
+/// Finds the JSON front matter members, without the delimiters.
+///
+/// With the default brace delimiters, the block ends at the brace that
+/// closes the opening one, so that nested objects are kept whole. Custom
+/// delimiters end at their first occurrence.
+fn json_block<'a>(
+    content: &'a str,
+    options: &ExtractOptions,
+) -> Option<&'a str> {
+    let delimiters = &options.json_delimiters;
+    if delimiters.open == "{" && delimiters.close == "}" {
+        let start = content.trim_start();
+        if !start.starts_with('{') {
+            return None;
+        }
+        let end = json_object_end(start)?;
+        return start.get(1..end - 1).map(str::trim);
+    }
+
+    let pattern = format!(
+        r"(?s)^\s*{}\s*(.*?)\s*{}",
+        regex::escape(&delimiters.open),
+        regex::escape(&delimiters.close)
+    );
+    let re = Regex::new(&pattern).ok()?;
+    re.captures(content)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str().trim())
+}
+
+/// Parses JSON front matter, falling back to JSON5 with the `json5` feature.
+///
+/// The JSON5 fallback accepts comments, trailing commas, and unquoted keys,
+/// which are common in hand-authored front matter.
+fn parse_json_front_matter(json_str: &str) -> Option<JsonValue> {
+    match serde_json::from_str(json_str) {
+        Ok(value) => Some(value),
+        #[cfg(feature = "json5")]
+        Err(_) => json5::from_str(json_str).ok(),
+        #[cfg(not(feature = "json5"))]
+        Err(_) => None,
+    }
+}
+
+/// Flattens a JSON object the way YAML and TOML front matter is flattened.
+///
+/// Nested objects become dotted keys, arrays of objects become indexed
+/// keys such as `authors.0.name`, and arrays of scalars become inline
+/// lists. Null members are skipped.
+fn flatten_json(
+    value: &JsonValue,
+    max_depth: usize,
+) -> Result<HashMap<String, String>, MetadataError> {
+    let mut map = HashMap::new();
+    flatten_json_recursive(
+        value,
+        String::new(),
+        &mut map,
+        0,
+        max_depth,
+    )?;
+    Ok(map)
+}
+
+/// Flattens a JSON value into the crate's dotted-key convention.
+///
+/// This applies the same rules as YAML, TOML, and JSON front matter:
+/// nested objects become dotted keys such as `author.name`, arrays of
+/// objects become indexed keys such as `authors.0.name`, and arrays of
+/// scalars become inline lists such as `[rust, seo]`. Null members are
+/// skipped. The result can be passed straight to `generate_metatags`.
+///
+/// Objects may be nested at most `DEFAULT_MAX_DEPTH` levels deep, as in
+/// front matter extraction; use `flatten_json_value_with_depth` to set
+/// another limit. A value that is not an object yields an empty map.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value to flatten.
+///
+/// # Returns
+///
+/// A `Result` containing a `HashMap` of flattened keys to string values.
+///
+/// # Errors
+///
+/// Returns a `MetadataError::ProcessingError` if the value is nested
+/// deeper than `DEFAULT_MAX_DEPTH`.
+///
+/// # Examples
+///
+/// ```
+/// use metadata_gen::metadata::flatten_json_value;
+/// use serde_json::json;
+///
+/// let map = flatten_json_value(&json!({
+///     "title": "Hello",
+///     "author": { "name": "Jane" },
+///     "tags": ["rust", "seo"]
+/// }))
+/// .unwrap();
+/// assert_eq!(map["author.name"], "Jane");
+/// assert_eq!(map["tags"], "[rust, seo]");
+/// ```
+pub fn flatten_json_value(
+    value: &JsonValue,
+) -> Result<HashMap<String, String>, MetadataError> {
+    flatten_json_value_with_depth(value, DEFAULT_MAX_DEPTH)
+}
+
+/// Flattens a JSON value like `flatten_json_value`, with a custom
+/// nesting limit.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value to flatten.
+/// * `max_depth` - The maximum nesting depth of objects.
+///
+/// # Returns
+///
+/// A `Result` containing a `HashMap` of flattened keys to string values.
+///
+/// # Errors
+///
+/// Returns a `MetadataError::ProcessingError` if the value is nested
+/// deeper than `max_depth`.
+pub fn flatten_json_value_with_depth(
+    value: &JsonValue,
+    max_depth: usize,
+) -> Result<HashMap<String, String>, MetadataError> {
+    if !value.is_object() {
+        return Ok(HashMap::new());
+    }
+    flatten_json(value, max_depth)
+}
+
+fn flatten_json_recursive(
+    value: &JsonValue,
+    prefix: String,
+    map: &mut HashMap<String, String>,
+    depth: usize,
+    max_depth: usize,
+) -> Result<(), MetadataError> {
+    match value {
+        JsonValue::Object(object) => {
+            if depth >= max_depth {
+                return Err(max_depth_error(max_depth));
+            }
+            for (k, v) in object {
+                let new_prefix = if prefix.is_empty() {
+                    k.to_string()
+                } else {
+                    format!("{}.{}", prefix, k)
+                };
+                flatten_json_recursive(
+                    v,
+                    new_prefix,
+                    map,
+                    depth + 1,
+                    max_depth,
+                )?;
+            }
+        }
+        JsonValue::Array(arr) => {
+            // Objects inside an array are flattened to indexed keys
+            // such as `authors.0.name`.
+            for (index, item) in arr.iter().enumerate() {
+                if item.is_object() {
+                    flatten_json_recursive(
+                        item,
+                        format!("{}.{}", prefix, index),
+                        map,
+                        depth + 1,
+                        max_depth,
+                    )?;
+                }
+            }
+            if !arr.is_empty() && arr.iter().all(JsonValue::is_object) {
+                return Ok(());
+            }
+
+            let items = arr
+                .iter()
+                .filter_map(json_scalar_to_string)
+                .collect::<Vec<String>>();
+            let inline_list =
+                format_inline_list(items.iter().map(String::as_str));
+            map.insert(prefix, inline_list);
+        }
+        JsonValue::Null => {}
+        _ => {
+            if let Some(scalar) = json_scalar_to_string(value) {
+                map.insert(prefix, scalar);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Converts a JSON string, number, or boolean to its string representation.
+fn json_scalar_to_string(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(s) => Some(s.clone()),
+        JsonValue::Number(n) => Some(n.to_string()),
+        JsonValue::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Extracts metadata from a leading reStructuredText field list.
+///
+/// The field list is the block of `:key: value` lines at the start of the
+/// content, as used for document metadata by Sphinx and docutils. A field
+/// value may continue on the following lines if they are indented; those
+/// lines are joined with single spaces. The block ends at the first blank
+/// line or line that is not a field or continuation.
+///
+/// `extract_metadata` falls back to this when no YAML, TOML, or JSON front
+/// matter is found.
+///
+/// # Arguments
+///
+/// * `content` - A string slice containing the content to extract metadata from.
+///
+/// # Returns
+///
+/// The extracted `Metadata`, or `None` if the content does not start with
+/// a field list.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::metadata::extract_rst_metadata;
+///
+/// let content = ":title: My Page\n:abstract: A summary that\n    continues here.\n\nBody";
+/// let metadata = extract_rst_metadata(content).unwrap();
+/// assert_eq!(metadata.get("title").unwrap(), "My Page");
+/// assert_eq!(
+///     metadata.get("abstract").unwrap(),
+///     "A summary that continues here."
+/// );
+/// ```
+pub fn extract_rst_metadata(content: &str) -> Option<Metadata> {
+    rst_field_list_block(content).map(parse_rst_field_list)
+}
+
+/// Finds the source text of a leading reStructuredText field list.
+///
+/// The block runs from the first field to the end of the last field or
+/// continuation line. The fields are only recognized, not parsed, so the
+/// size of the block can be checked against `ExtractOptions` first.
+fn rst_field_list_block(content: &str) -> Option<&str> {
+    let content = content.strip_prefix('\u{FEFF}').unwrap_or(content);
+    let mut offset = 0;
+    let mut start = None;
+    let mut end = 0;
+
+    for raw_line in content.split_inclusive('\n') {
+        let line_start = offset;
+        offset += raw_line.len();
+        let line = raw_line.strip_suffix('\n').unwrap_or(raw_line);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+
+        if line.trim().is_empty() {
+            if start.is_none() {
+                continue;
+            }
+            break;
+        }
+
+        let is_continuation = line.starts_with([' ', '\t']);
+        if !(start.is_some() && is_continuation)
+            && parse_rst_field(line).is_none()
+        {
+            break;
+        }
+        // Leading blank lines are not part of the block.
+        start.get_or_insert(line_start);
+        end = offset;
+    }
+
+    content.get(start?..end)
+}
+
+/// Parses a field list block found by `rst_field_list_block`.
+///
+/// Continuation lines are joined to the value of the preceding field
+/// with a single space.
+fn parse_rst_field_list(block: &str) -> Metadata {
+    let mut map: HashMap<String, String> = HashMap::new();
+    let mut current: Option<(String, String)> = None;
+
+    for line in block.lines() {
+        match (current.as_mut(), parse_rst_field(line)) {
+            (Some((_, value)), _) if line.starts_with([' ', '\t']) => {
+                if !value.is_empty() {
+                    value.push(' ');
+                }
+                value.push_str(line.trim());
+            }
+            (_, Some((key, value))) => {
+                if let Some((key, value)) = current
+                    .replace((key.to_string(), value.to_string()))
+                {
+                    map.insert(key, value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some((key, value)) = current {
+        map.insert(key, value);
+    }
+    Metadata::new(map)
+}
+
+/// Parses a single `:key: value` line of a reStructuredText field list.
+fn parse_rst_field(line: &str) -> Option<(&str, &str)> {
+    let (name, value) = line.strip_prefix(':')?.split_once(':')?;
+    if name.trim().is_empty() || name.starts_with(char::is_whitespace) {
+        return None;
+    }
+    if !value.is_empty() && !value.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some((name.trim(), value.trim()))
+}
+
+/// Processes the extracted metadata.
+///
+/// This function standardizes dates, ensures required fields are present, and generates derived fields.
+///
+/// # Arguments
+///
+/// * `metadata` - A reference to the `Metadata` instance to process.
+///
+/// # Returns
+///
+/// A `Result` containing the processed `Metadata` if successful, or a `MetadataError` if processing fails.
+///
+/// # Errors
+///
+/// Returns a `MetadataError` if date standardization fails or if required fields are missing.
+pub fn process_metadata(
+    metadata: &Metadata,
+) -> Result<Metadata, MetadataError> {
+    process_metadata_with_options(metadata, &ProcessOptions::default())
+}
+
+/// Parses date strings into their year, month, and day components.
+///
+/// Implement this trait to plug a different date library, or bespoke
+/// date formats, into `process_metadata_with_options`.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::error::MetadataError;
+/// use metadata_gen::metadata::DateParser;
+///
+/// /// Parses compact `YYYYMMDD` dates.
+/// struct CompactDateParser;
+///
+/// impl DateParser for CompactDateParser {
+///     fn parse_to_ymd(
+///         &self,
+///         input: &str,
+///     ) -> Result<(i32, u8, u8), MetadataError> {
+///         let invalid = || MetadataError::DateParseError(input.to_string());
+///         let year = input.get(0..4).and_then(|y| y.parse().ok());
+///         let month = input.get(4..6).and_then(|m| m.parse().ok());
+///         let day = input.get(6..8).and_then(|d| d.parse().ok());
+///         match (year, month, day) {
+///             (Some(y), Some(m), Some(d)) => Ok((y, m, d)),
+///             _ => Err(invalid()),
+///         }
+///     }
+/// }
+///
+/// assert_eq!(CompactDateParser.parse_to_ymd("20230520").unwrap(), (2023, 5, 20));
+/// ```
+pub trait DateParser {
+    /// Parses `input` into a `(year, month, day)` tuple.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MetadataError::DateParseError` if the input cannot be parsed.
+    fn parse_to_ymd(
+        &self,
+        input: &str,
+    ) -> Result<(i32, u8, u8), MetadataError>;
+}
+
+/// The default `DateParser`, backed by the `dtt` crate.
+///
+/// Accepts ISO 8601 / RFC 3339 dates, `YYYY-MM-DD`, `DD/MM/YYYY`,
+/// `MM/DD/YYYY`, and relative dates such as `today` or `3 days ago`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DttDateParser;
+
+impl DateParser for DttDateParser {
+    fn parse_to_ymd(
+        &self,
+        input: &str,
+    ) -> Result<(i32, u8, u8), MetadataError> {
+        let date = parse_date_relative_to(input, &DateTime::new())?;
+        Ok((date.year(), date.month() as u8, date.day()))
+    }
+}
+
+/// What processing does with a `date` that cannot be parsed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DateErrorPolicy {
+    /// Processing fails with a `MetadataError::DateParseError`.
+    #[default]
+    Fail,
+    /// The date is left as it is.
+    Keep,
+    /// The date is removed.
+    Drop,
+}
+
+/// Options controlling how metadata is processed.
+pub struct ProcessOptions<'a> {
+    /// The parser used to standardize the `date` field.
+    pub date_parser: &'a dyn DateParser,
+    /// The separator used for the derived `slug`. Defaults to `None`.
+    ///
+    /// When `None`, the slug is generated as by `process_metadata`, for
+    /// compatibility: the title is lowercased and each space is replaced
+    /// with `-`, so `"  Spaces  "` becomes `--spaces--`, and punctuation is
+    /// kept. When set, the slug is generated with `generate_slug_with`,
+    /// which collapses consecutive separators and trims leading and
+    /// trailing ones, so the same title becomes `spaces`. Use `Some('-')`
+    /// for that cleaner slug with the default separator.
+    pub slug_separator: Option<char>,
+    /// Whether the derived `slug` is transliterated to ASCII, as by
+    /// `SlugOptions::ascii_only`. Defaults to `false`.
+    pub slug_ascii_only: bool,
+    /// Whether required fields that are empty or contain only whitespace
+    /// are treated as missing. Defaults to `false`.
+    pub treat_empty_as_missing: bool,
+    /// What to do with a `date` that `date_parser` cannot parse. Defaults
+    /// to `DateErrorPolicy::Fail`. The required field check runs before
+    /// a date is dropped, so `Drop` does not cause a missing field error.
+    pub date_on_error: DateErrorPolicy,
+}
+
+impl Default for ProcessOptions<'_> {
+    fn default() -> Self {
+        ProcessOptions {
+            date_parser: &DttDateParser,
+            slug_separator: None,
+            slug_ascii_only: false,
+            treat_empty_as_missing: false,
+            date_on_error: DateErrorPolicy::Fail,
+        }
+    }
+}
+
+impl std::fmt::Debug for ProcessOptions<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProcessOptions").finish_non_exhaustive()
+    }
+}
+
+/// Processes the extracted metadata using the given options.
+///
+/// This function behaves like `process_metadata`, but standardizes the
+/// `date` field with `options.date_parser`.
+///
+/// # Arguments
+///
+/// * `metadata` - A reference to the `Metadata` instance to process.
+/// * `options` - The `ProcessOptions` controlling processing.
+///
+/// # Returns
+///
+/// A `Result` containing the processed `Metadata` if successful, or a `MetadataError` if processing fails.
+///
+/// # Errors
+///
+/// Returns a `MetadataError` if date standardization fails or if required fields are missing.
+pub fn process_metadata_with_options(
+    metadata: &Metadata,
+    options: &ProcessOptions<'_>,
+) -> Result<Metadata, MetadataError> {
+    let mut processed = metadata.clone();
+    process_metadata_in_place_with_options(&mut processed, options)?;
+    Ok(processed)
+}
+
+/// Processes the metadata in place.
+///
+/// This function behaves like `process_metadata`, but updates `metadata`
+/// directly instead of returning a processed copy, which avoids cloning
+/// large maps.
+///
+/// # Arguments
+///
+/// * `metadata` - A mutable reference to the `Metadata` instance to process.
+///
+/// # Errors
+///
+/// Returns a `MetadataError` if date standardization fails or if required
+/// fields are missing. On error, `metadata` is left unchanged.
+pub fn process_metadata_in_place(
+    metadata: &mut Metadata,
+) -> Result<(), MetadataError> {
+    process_metadata_in_place_with_options(
+        metadata,
+        &ProcessOptions::default(),
+    )
+}
+
+/// Processes the metadata in place using the given options.
+///
+/// # Arguments
+///
+/// * `metadata` - A mutable reference to the `Metadata` instance to process.
+/// * `options` - The `ProcessOptions` controlling processing.
+///
+/// # Errors
+///
+/// Returns a `MetadataError` if date standardization fails or if required
+/// fields are missing. On error, `metadata` is left unchanged.
+pub fn process_metadata_in_place_with_options(
+    metadata: &mut Metadata,
+    options: &ProcessOptions<'_>,
+) -> Result<(), MetadataError> {
+    // Convert dates to a standard format
+    let date = if options.treat_empty_as_missing {
+        metadata.get_non_empty("date")
+    } else {
+        metadata.get("date")
+    };
+    // `Some(None)` means that the date is to be dropped.
+    let standardized_date = match date
+        .map(|date| standardize_date(date, options.date_parser))
+    {
+        Some(Ok(date)) => Some(Some(date)),
+        Some(Err(e)) => match options.date_on_error {
+            DateErrorPolicy::Fail => return Err(e),
+            DateErrorPolicy::Keep => None,
+            DateErrorPolicy::Drop => Some(None),
+        },
+        None => None,
+    };
+
+    // Ensure required fields are present
+    ensure_required_fields(metadata, options.treat_empty_as_missing)?;
+
+    match standardized_date {
+        Some(Some(date)) => {
+            metadata.insert("date".to_string(), date);
+        }
+        Some(None) => {
+            metadata.inner.remove("date");
+        }
+        None => {}
+    }
+
+    // Generate derived fields
+    generate_derived_fields(metadata, options);
+
+    Ok(())
+}
+
+/// Standardizes the date format.
+///
+/// This function parses the date with the given `DateParser` and converts it to the YYYY-MM-DD format.
+///
+/// # Arguments
+///
+/// * `date` - A string slice containing the date to standardize.
+/// * `parser` - The `DateParser` used to parse the date.
+///
+/// # Returns
+///
+/// A `Result` containing the standardized date string if successful, or a `MetadataError` if parsing fails.
+///
+/// # Errors
+///
+/// Returns a `MetadataError::DateParseError` if the date cannot be parsed or is invalid.
+fn standardize_date(
+    date: &str,
+    parser: &dyn DateParser,
+) -> Result<String, MetadataError> {
+    let (year, month, day) = parser.parse_to_ymd(date)?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return Err(MetadataError::DateParseError(format!(
+            "Invalid date components: {}-{}-{}",
+            year, month, day
+        )));
+    }
+    Ok(format!("{:04}-{:02}-{:02}", year, month, day))
+}
+
+/// Parses a date, resolving relative dates against `reference`.
+///
+/// # Errors
+///
+/// Returns a `MetadataError::DateParseError` if the date cannot be parsed or is invalid.
+fn parse_date_relative_to(
+    date: &str,
+    reference: &DateTime,
+) -> Result<DateTime, MetadataError> {
+    if relative_day_offset(date).is_some() {
+        return resolve_relative_date(date, reference);
+    }
+
+    // Handle edge cases with empty or too-short dates
+    if date.trim().is_empty() {
+        return Err(MetadataError::DateParseError(
+            "Date string is empty.".to_string(),
+        ));
+    }
+
+    if date.len() < 8 {
+        return Err(MetadataError::DateParseError(
+            "Date string is too short.".to_string(),
+        ));
+    }
+
+    // Check if the date is in the DD/MM/YYYY format and reformat to YYYY-MM-DD
+    let date = if date.contains('/') && date.len() == 10 {
+        let parts: Vec<&str> = date.split('/').collect();
+        match parts.as_slice() {
+            [day, month, year]
+                if day.len() == 2
+                    && month.len() == 2
+                    && year.len() == 4 =>
+            {
+                format!("{}-{}-{}", year, month, day) // Reformat to YYYY-MM-DD
+            }
+            _ => {
+                return Err(MetadataError::DateParseError(
+                    "Invalid DD/MM/YYYY date format.".to_string(),
+                ));
+            }
+        }
+    } else {
+        date.to_string()
+    };
+
+    // Attempt to parse the date in different formats using DateTime methods
+    let parsed_date = DateTime::parse(&date)
+        .or_else(|_| {
+            DateTime::parse_custom_format(&date, "[year]-[month]-[day]")
+        })
+        .or_else(|_| {
+            DateTime::parse_custom_format(&date, "[month]/[day]/[year]")
+        })
+        .map_err(|e| {
+            MetadataError::DateParseError(format!(
+                "Failed to parse date: {}",
+                e
+            ))
+        })?;
+
+    Ok(parsed_date)
+}
+
+/// Formats a `DateTime` as a `YYYY-MM-DD` string.
+fn format_ymd(date: &DateTime) -> String {
+    format!(
+        "{:04}-{:02}-{:02}",
+        date.year(),
+        date.month() as u8,
+        date.day()
+    )
+}
+
+/// Returns the day offset described by a relative date expression.
+///
+/// Recognizes `today`, `yesterday`, `tomorrow`, and `N day(s) ago`,
+/// ignoring case and surrounding whitespace. The count `N` must be a
+/// non-negative integer, so `-3 days ago` is rejected.
+pub(crate) fn relative_day_offset(input: &str) -> Option<i64> {
+    let input = input.trim().to_lowercase();
+    match input.as_str() {
+        "today" => return Some(0),
+        "yesterday" => return Some(-1),
+        "tomorrow" => return Some(1),
+        _ => {}
+    }
+
+    let mut words = input.split_whitespace();
+    let count = i64::from(words.next()?.parse::<u32>().ok()?);
+    let unit = words.next()?;
+    let ago = words.next()?;
+    if words.next().is_some()
+        || !matches!(unit, "day" | "days")
+        || ago != "ago"
+    {
+        return None;
+    }
+    count.checked_neg()
+}
+
+/// Resolves a relative date expression to an absolute `YYYY-MM-DD` date.
+///
+/// Supported expressions are `today`, `yesterday`, `tomorrow`, and
+/// `N days ago`. The reference date is passed in explicitly rather than
+/// read from the system clock, which keeps the result deterministic.
+///
+/// # Arguments
+///
+/// * `input` - The relative date expression.
+/// * `reference` - The date that the expression is resolved against.
+///
+/// # Returns
+///
+/// A `Result` containing the resolved date in `YYYY-MM-DD` format.
+///
+/// # Errors
+///
+/// Returns a `MetadataError::DateParseError` if the expression is not a
+/// supported relative date or the resolved date is out of range.
+///
+/// # Example
+///
+/// ```
+/// use dtt::datetime::DateTime;
+/// use metadata_gen::metadata::parse_relative_date;
+///
+/// let reference = DateTime::parse("2024-03-01T00:00:00Z").unwrap();
+/// assert_eq!(
+///     parse_relative_date("yesterday", &reference).unwrap(),
+///     "2024-02-29"
+/// );
+/// ```
+pub fn parse_relative_date(
+    input: &str,
+    reference: &DateTime,
+) -> Result<String, MetadataError> {
+    resolve_relative_date(input, reference)
+        .map(|date| format_ymd(&date))
+}
+
+/// Resolves a relative date expression against `reference`.
+fn resolve_relative_date(
+    input: &str,
+    reference: &DateTime,
+) -> Result<DateTime, MetadataError> {
+    let offset = relative_day_offset(input).ok_or_else(|| {
+        MetadataError::DateParseError(format!(
+            "Unrecognized relative date: {}",
+            input
+        ))
+    })?;
+
+    reference.add_days(offset).map_err(|e| {
+        MetadataError::DateParseError(format!(
+            "Failed to resolve relative date: {}",
+            e
+        ))
+    })
+}
+
+/// The kind of value expected in a metadata field.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FieldKind {
+    /// Any text.
+    #[default]
+    Text,
+    /// A date accepted by `DttDateParser`.
+    Date,
+    /// An absolute `http` or `https` URL.
+    Url,
+    /// An email address.
+    Email,
+    /// A sitemap change frequency, one of `SITEMAP_CHANGE_FREQUENCIES`.
+    ChangeFrequency,
+    /// A sitemap priority between `0.0` and `1.0` inclusive.
+    Priority,
+}
+
+/// The change frequencies allowed in a sitemap `<changefreq>` element.
+pub const SITEMAP_CHANGE_FREQUENCIES: [&str; 7] = [
+    "always", "hourly", "daily", "weekly", "monthly", "yearly", "never",
+];
+
+/// A declarative validation rule for a single metadata field.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldRule {
+    /// Whether the field must be present.
+    pub required: bool,
+    /// The maximum length of the value in characters, if any.
+    pub max_len: Option<usize>,
+    /// The kind of value expected in the field.
+    pub kind: FieldKind,
+}
+
+/// Validates metadata against a set of per-field rules.
+///
+/// Every rule is checked, and all failures are collected rather than
+/// stopping at the first one. Fields are checked in the alphabetical order
+/// of their keys, so the errors are returned in a stable order.
+///
+/// # Arguments
+///
+/// * `metadata` - A reference to the `Metadata` instance to validate.
+/// * `rules` - A map from field key to the `FieldRule` for that field.
+///
+/// # Returns
+///
+/// `Ok(())` if every rule is satisfied, or `Err` with one `MetadataError`
+/// per failure: a `MissingFieldError` for missing required fields, and a
+/// `ValidationError` for values that are too long or of the wrong kind.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::metadata::{
+///     validate_with_rules, FieldKind, FieldRule, Metadata,
+/// };
+/// use std::collections::HashMap;
+///
+/// let mut rules = HashMap::new();
+/// rules.insert(
+///     "title".to_string(),
+///     FieldRule { required: true, max_len: Some(70), kind: FieldKind::Text },
+/// );
+///
+/// let mut metadata = Metadata::new(HashMap::new());
+/// metadata.insert("title".to_string(), "Hello".to_string());
+/// assert!(validate_with_rules(&metadata, &rules).is_ok());
+/// ```
+pub fn validate_with_rules(
+    metadata: &Metadata,
+    rules: &HashMap<String, FieldRule>,
+) -> Result<(), Vec<MetadataError>> {
+    let mut rules = rules.iter().collect::<Vec<_>>();
+    rules.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut errors = Vec::new();
+    for (field, rule) in rules {
+        let value = match metadata.get(field) {
+            Some(value) => value,
+            None => {
+                if rule.required {
+                    errors.push(MetadataError::MissingFieldError(
+                        field.clone(),
+                    ));
+                }
+                continue;
+            }
+        };
+
+        if let Some(max_len) = rule.max_len {
+            let len = value.chars().count();
+            if len > max_len {
+                errors.push(MetadataError::new_validation_error(
+                    field.as_str(),
+                    format!(
+                        "Value is {} characters long, exceeding the maximum of {}",
+                        len, max_len
+                    ),
+                ));
+            }
+        }
+
+        let kind_error = match rule.kind {
+            FieldKind::Date
+                if standardize_date(value, &DttDateParser).is_err() =>
+            {
+                Some("Value is not a valid date")
+            }
+            FieldKind::Url if !is_valid_url(value) => {
+                Some("Value is not a valid URL")
+            }
+            FieldKind::Email if !is_valid_email(value) => {
+                Some("Value is not a valid email address")
+            }
+            FieldKind::ChangeFrequency
+                if !is_valid_change_frequency(value) =>
+            {
+                Some("Value is not a valid sitemap change frequency")
+            }
+            FieldKind::Priority if !is_valid_priority(value) => Some(
+                "Value is not a sitemap priority between 0.0 and 1.0",
+            ),
+            _ => None,
+        };
+        if let Some(message) = kind_error {
+            errors.push(MetadataError::new_validation_error(
+                field.as_str(),
+                message,
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Validates the sitemap fields of the metadata.
+///
+/// The `changefreq` field, if present, must be one of
+/// `SITEMAP_CHANGE_FREQUENCIES`, and the `priority` field, if present, must
+/// be a number between `0.0` and `1.0` inclusive. Search engines reject
+/// sitemaps with other values.
+///
+/// # Arguments
+///
+/// * `metadata` - A reference to the `Metadata` instance to validate.
+///
+/// # Returns
+///
+/// `Ok(())` if the sitemap fields are valid or absent.
+///
+/// # Errors
+///
+/// Returns a `MetadataError::ValidationError` naming the first invalid
+/// field, checking `changefreq` before `priority`.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::metadata::{validate_sitemap_fields, Metadata};
+/// use std::collections::HashMap;
+///
+/// let mut metadata = Metadata::new(HashMap::new());
+/// metadata.insert("changefreq".to_string(), "weekly".to_string());
+/// metadata.insert("priority".to_string(), "0.8".to_string());
+/// assert!(validate_sitemap_fields(&metadata).is_ok());
+///
+/// metadata.insert("priority".to_string(), "1.5".to_string());
+/// assert!(validate_sitemap_fields(&metadata).is_err());
+/// ```
+pub fn validate_sitemap_fields(
+    metadata: &Metadata,
+) -> Result<(), MetadataError> {
+    let rules = [
+        ("changefreq", FieldKind::ChangeFrequency),
+        ("priority", FieldKind::Priority),
+    ]
+    .into_iter()
+    .map(|(field, kind)| {
+        (
+            field.to_string(),
+            FieldRule {
+                kind,
+                ..FieldRule::default()
+            },
+        )
+    })
+    .collect();
+
+    validate_with_rules(metadata, &rules).map_err(|errors| {
+        errors.into_iter().next().unwrap_or_else(|| {
+            MetadataError::new_validation_error(
+                "sitemap",
+                "Invalid sitemap fields",
+            )
+        })
+    })
+}
+
+/// Checks that a value is an absolute `http` or `https` URL with a host.
+fn is_valid_url(value: &str) -> bool {
+    let rest = match value
+        .strip_prefix("https://")
+        .or_else(|| value.strip_prefix("http://"))
+    {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    !host.is_empty() && !value.contains(char::is_whitespace)
+}
+
+/// Checks that a value is one of `SITEMAP_CHANGE_FREQUENCIES`.
+fn is_valid_change_frequency(value: &str) -> bool {
+    SITEMAP_CHANGE_FREQUENCIES.contains(&value.trim())
+}
+
+/// Checks that a value is a number between `0.0` and `1.0` inclusive.
+fn is_valid_priority(value: &str) -> bool {
+    value
+        .trim()
+        .parse::<f64>()
+        .map_or(false, |priority| (0.0..=1.0).contains(&priority))
+}
+
+/// Checks that a value looks like an email address.
+fn is_valid_email(value: &str) -> bool {
+    match value.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !domain.contains('@')
+                && !value.contains(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+/// Ensures that all required fields are present in the metadata.
+///
+/// # Arguments
+///
+/// * `metadata` - A reference to the `Metadata` instance to check.
+/// * `treat_empty_as_missing` - Whether empty or whitespace-only fields count as missing.
+///
+/// # Returns
+///
+/// A `Result<()>` if all required fields are present, or a `MetadataError` if any are missing.
+///
+/// # Errors
+///
+/// Returns a `MetadataError::MissingFieldError` if any required field is missing.
+fn ensure_required_fields(
+    metadata: &Metadata,
+    treat_empty_as_missing: bool,
+) -> Result<(), MetadataError> {
+    let required_fields = ["title", "date"];
+
+    for &field in &required_fields {
+        let present = if treat_empty_as_missing {
+            metadata.get_non_empty(field).is_some()
+        } else {
+            metadata.contains_key(field)
+        };
+        if !present {
+            return Err(MetadataError::MissingFieldError(
+                field.to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates derived fields for the metadata.
+///
+/// Currently, this function generates a URL slug from the title if not already present.
+///
+/// # Arguments
+///
+/// * `metadata` - A mutable reference to the `Metadata` instance to update.
+/// * `options` - The `ProcessOptions` controlling slug generation.
+fn generate_derived_fields(
+    metadata: &mut Metadata,
+    options: &ProcessOptions<'_>,
+) {
+    if !metadata.contains_key("slug") {
+        if let Some(title) = metadata.get("title") {
+            let slug =
+                match (options.slug_separator, options.slug_ascii_only)
+                {
+                    (None, false) => generate_slug(title),
+                    (separator, ascii_only) => {
+                        generate_slug_with_options(
+                            title,
+                            &SlugOptions {
+                                separator: separator.unwrap_or('-'),
+                                ascii_only,
+                            },
+                        )
+                    }
+                };
+            metadata.insert("slug".to_string(), slug);
+        }
+    }
+}
+
+/// Generates a URL slug from the given title.
+///
+/// # Arguments
+///
+/// * `title` - A string slice containing the title to convert to a slug.
+///
+/// # Returns
+///
+/// A `String` containing the generated slug.
+fn generate_slug(title: &str) -> String {
+    title.to_lowercase().replace(' ', "-")
+}
+
+/// Generates a URL slug from the given title using a custom separator.
+///
+/// This is `generate_slug_with_options` with `ascii_only` unset: Unicode
+/// letters are kept, whitespace is replaced with `separator`, and
+/// URL-unsafe punctuation is removed. Consecutive separators are
+/// collapsed into one, and leading and trailing separators are removed.
+///
+/// # Arguments
+///
+/// * `title` - A string slice containing the title to convert to a slug.
+/// * `separator` - The character placed between words.
+///
+/// # Returns
+///
+/// A `String` containing the generated slug.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::metadata::generate_slug_with;
+///
+/// assert_eq!(generate_slug_with("  Hello   World ", '_'), "hello_world");
+/// ```
+pub fn generate_slug_with(title: &str, separator: char) -> String {
+    generate_slug_with_options(
+        title,
+        &SlugOptions {
+            separator,
+            ascii_only: false,
+        },
+    )
+}
+
+/// Options controlling `generate_slug_with_options`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlugOptions {
+    /// The character placed between words, `-` by default.
+    pub separator: char,
+    /// Whether to transliterate the title to ASCII, so that `Crème brûlée`
+    /// becomes `creme-brulee`. When `false`, letters of any script, such as
+    /// Cyrillic or CJK, are kept as they are. Defaults to `false`.
+    pub ascii_only: bool,
+}
+
+impl Default for SlugOptions {
+    fn default() -> Self {
+        SlugOptions {
+            separator: '-',
+            ascii_only: false,
+        }
+    }
+}
+
+/// Generates a URL slug from the given title using the given options.
+///
+/// The title is lowercased, and transliterated to ASCII if
+/// `options.ascii_only` is set. Whitespace, `-`, `_`, and the separator
+/// itself separate words; other punctuation and symbols are removed.
+/// Words are joined with `options.separator`, so consecutive separators
+/// are collapsed and leading and trailing ones are removed.
+///
+/// # Arguments
+///
+/// * `title` - A string slice containing the title to convert to a slug.
+/// * `options` - The `SlugOptions` controlling the slug.
+///
+/// # Returns
+///
+/// A `String` containing the generated slug.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::metadata::{generate_slug_with_options, SlugOptions};
+///
+/// let unicode = SlugOptions::default();
+/// assert_eq!(generate_slug_with_options("Привет, мир!", &unicode), "привет-мир");
+///
+/// let ascii = SlugOptions { ascii_only: true, ..SlugOptions::default() };
+/// assert_eq!(generate_slug_with_options("Crème Brûlée", &ascii), "creme-brulee");
+/// ```
+pub fn generate_slug_with_options(
+    title: &str,
+    options: &SlugOptions,
+) -> String {
+    let title = if options.ascii_only {
+        deunicode::deunicode(title)
+    } else {
+        title.to_string()
+    };
+    let separator = options.separator;
+
+    let mut words: Vec<String> = Vec::new();
+    let mut word = String::new();
+    for c in title.to_lowercase().chars() {
+        if c.is_whitespace() || c == '-' || c == '_' || c == separator {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+        } else if is_slug_char(c, options.ascii_only) {
+            word.push(c);
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words.join(separator.encode_utf8(&mut [0; 4]))
+}
+
+/// Returns `true` if a character is kept in a slug word.
+///
+/// ASCII letters and digits are always kept. Other characters are kept
+/// unless `ascii_only` is set or they are in a Unicode punctuation or
+/// symbol block.
+fn is_slug_char(c: char, ascii_only: bool) -> bool {
+    if c.is_ascii() {
+        return c.is_ascii_alphanumeric();
+    }
+    !ascii_only
+        && !matches!(
+            c,
+            '\u{00A0}'..='\u{00BF}'
+                | '\u{00D7}'
+                | '\u{00F7}'
+                | '\u{2000}'..='\u{2BFF}'
+                | '\u{3000}'..='\u{303F}'
+                | '\u{FE10}'..='\u{FE6F}'
+                | '\u{FF00}'..='\u{FF0F}'
+                | '\u{FF1A}'..='\u{FF20}'
+                | '\u{FF3B}'..='\u{FF40}'
+                | '\u{FF5B}'..='\u{FF65}'
+        )
+}
+
+/// The default maximum length of a generated `description`.
+pub const DEFAULT_DESCRIPTION_LENGTH: usize = 160;
+
+/// Generates a description from the first paragraph of the content.
+///
+/// The front matter is removed with `strip_front_matter`, and the first
+/// paragraph of the body that is not a heading, code block, HTML block,
+/// table, or thematic break is used. Markdown emphasis, code spans, links,
+/// images, and list and quote markers are stripped, whitespace is
+/// collapsed, and the text is truncated at a word boundary so that it is
+/// at most `max_len` characters long.
+///
+/// # Arguments
+///
+/// * `content` - A string slice containing the content.
+/// * `max_len` - The maximum length of the description, in characters.
+///
+/// # Returns
+///
+/// The generated description, which is empty if no paragraph is found.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::metadata::generate_description;
+///
+/// let content = "---\ntitle: Hi\n---\n# Hi\n\nA **bold** [claim](/claims).";
+/// assert_eq!(generate_description(content, 160), "A bold claim.");
+/// assert_eq!(generate_description(content, 8), "A bold");
+/// ```
+pub fn generate_description(content: &str, max_len: usize) -> String {
+    let body = strip_front_matter(content).replace("\r\n", "\n");
+
+    let mut in_fence = false;
+    let mut paragraph: Vec<&str> = Vec::new();
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        if trimmed.is_empty() {
+            if paragraph.is_empty() {
+                continue;
+            }
+            break;
+        }
+        if paragraph.is_empty() && !is_prose_line(trimmed) {
+            continue;
+        }
+        paragraph.push(trimmed);
+    }
+
+    let text = strip_markdown(&paragraph.join(" "));
+    truncate_on_word_boundary(&text, max_len)
+}
+
+/// Returns `true` if a line can start a prose paragraph.
+fn is_prose_line(line: &str) -> bool {
+    let is_rule = line.len() >= 3
+        && ['-', '*', '_', '='].iter().any(|&marker| {
+            line.chars().all(|c| c == marker || c == ' ')
+        });
+    !(is_rule
+        || line.starts_with('#')
+        || line.starts_with('<')
+        || line.starts_with('|')
+        || line.starts_with("    ")
+        || (line.starts_with("![") && line.ends_with(')')))
+}
+
+/// Removes common inline and block Markdown syntax from text.
+fn strip_markdown(text: &str) -> String {
+    let patterns = [
+        (r"!\[[^\]]*\]\([^)]*\)", ""),
+        (r"\[([^\]]*)\]\([^)]*\)", "$1"),
+        (r"\[([^\]]*)\]\[[^\]]*\]", "$1"),
+        (r"(?m)^\s*(?:>\s*)+", ""),
+        (r"(?m)^\s*(?:[-*+]|\d+[.)])\s+", ""),
+        (r"(\*\*|__|~~|\*|`)", ""),
+        (r"(?:^|\s)_([^_]+)_(?:\s|$)", " $1 "),
+    ];
+
+    let mut text = text.to_string();
+    for (pattern, replacement) in patterns {
+        if let Ok(re) = Regex::new(pattern) {
+            text = re.replace_all(&text, replacement).into_owned();
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Shortens text for use as a meta description.
+///
+/// Runs of whitespace are first collapsed to single spaces. Text that is
+/// at most `max_len` characters long is returned as-is. Longer text is cut
+/// after the last whole word that fits and `…` is appended, so that the
+/// result, including the ellipsis, is at most `max_len` characters long.
+/// Words are never split: if even the first word does not fit, only the
+/// ellipsis is returned.
+///
+/// # Arguments
+///
+/// * `text` - The text to shorten.
+/// * `max_len` - The maximum length of the description, in characters.
+///
+/// # Returns
+///
+/// The shortened description.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::metadata::generate_meta_description;
+///
+/// let text = "Learn   how to write\ngreat meta descriptions.";
+/// assert_eq!(generate_meta_description(text, 160), "Learn how to write great meta descriptions.");
+/// assert_eq!(generate_meta_description(text, 20), "Learn how to write…");
+/// ```
+pub fn generate_meta_description(text: &str, max_len: usize) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= max_len {
+        return text;
+    }
+    if max_len == 0 {
+        return String::new();
+    }
+
+    // Leave room for the ellipsis.
+    let budget = max_len - 1;
+    let mut description = String::new();
+    let mut length = 0;
+    for word in text.split(' ') {
+        let separator = usize::from(!description.is_empty());
+        let word_length = word.chars().count();
+        if length + separator + word_length > budget {
+            break;
+        }
+        if separator == 1 {
+            description.push(' ');
+        }
+        description.push_str(word);
+        length += separator + word_length;
+    }
+
+    format!(
+        "{}…",
+        description.trim_end_matches(|c: char| ",.;:-".contains(c))
+    )
+}
+
+/// Truncates text to at most `max_len` characters at a word boundary.
+///
+/// A single word longer than `max_len` is cut at `max_len` characters.
+fn truncate_on_word_boundary(text: &str, max_len: usize) -> String {
+    if text.chars().count() <= max_len {
+        return text.to_string();
+    }
+
+    let cut = text
+        .char_indices()
+        .nth(max_len)
+        .map_or(text.len(), |(index, _)| index);
+    let truncated = &text[..cut];
+    let truncated = if text[cut..].starts_with(' ') {
+        truncated
+    } else {
+        truncated
+            .rfind(' ')
+            .map_or(truncated, |space| &truncated[..space])
+    };
+    truncated
+        .trim_end_matches(|c: char| {
+            c.is_whitespace() || ",;:-".contains(c)
+        })
+        .to_string()
+}
+
+/// Makes a slug unique among the slugs generated so far.
+///
+/// If `slug` is not in `existing`, it is returned unchanged. Otherwise the
+/// first free suffix of `-2`, `-3`, and so on is appended. The returned
+/// slug is inserted into `existing`, so this can be called for each page
+/// while iterating over a site.
+///
+/// # Arguments
+///
+/// * `slug` - The slug to make unique.
+/// * `existing` - The slugs already in use.
+///
+/// # Returns
+///
+/// A `String` containing the unique slug.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::metadata::dedupe_slug;
+/// use std::collections::HashSet;
+///
+/// let mut existing = HashSet::new();
+/// assert_eq!(dedupe_slug("hello", &mut existing), "hello");
+/// assert_eq!(dedupe_slug("hello", &mut existing), "hello-2");
+/// ```
+pub fn dedupe_slug(
+    slug: &str,
+    existing: &mut HashSet<String>,
+) -> String {
+    let mut unique = slug.to_string();
+    let mut suffix = 2;
+    while existing.contains(&unique) {
+        unique = format!("{}-{}", slug, suffix);
+        suffix += 1;
+    }
+    existing.insert(unique.clone());
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dtt::dtt_parse;
+
+    #[test]
+    fn test_standardize_date() {
+        let test_cases = vec![
+            ("2023-05-20T15:30:00Z", "2023-05-20"),
+            ("2023-05-20", "2023-05-20"),
+            ("20/05/2023", "2023-05-20"), // European format DD/MM/YYYY
+        ];
+
+        for (input, expected) in test_cases {
+            let result = standardize_date(input, &DttDateParser);
+            assert!(result.is_ok(), "Failed for input: {}", input);
+            assert_eq!(result.unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_flatten_json_value() {
+        let value = serde_json::json!({
+            "title": "Hello",
+            "draft": false,
+            "views": 42,
+            "summary": null,
+            "author": { "name": "Jane", "social": { "x": "@jane" } },
+            "authors": [{ "name": "A" }, { "name": "B" }],
+            "tags": ["rust", "seo"]
+        });
+        let map = flatten_json_value(&value).unwrap();
+
+        assert_eq!(map.len(), 8);
+        assert_eq!(map["title"], "Hello");
+        assert_eq!(map["draft"], "false");
+        assert_eq!(map["views"], "42");
+        assert!(!map.contains_key("summary"));
+        assert_eq!(map["author.social.x"], "@jane");
+        assert_eq!(map["authors.1.name"], "B");
+        assert_eq!(map["tags"], "[rust, seo]");
+
+        let yaml: serde_yml::Value = serde_yml::from_str(
+            "title: Hello\nauthor:\n  name: Jane\nauthors:\n  - name: A\ntags: [rust, seo]\n",
+        )
+        .unwrap();
+        let json = serde_json::json!({
+            "title": "Hello",
+            "author": { "name": "Jane" },
+            "authors": [{ "name": "A" }],
+            "tags": ["rust", "seo"]
+        });
+        assert_eq!(
+            flatten_json_value(&json).unwrap(),
+            flatten_yaml(&yaml, DEFAULT_MAX_DEPTH).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_flatten_json_value_max_depth() {
+        let nested = |depth: usize| {
+            (0..depth).fold(
+                serde_json::json!("leaf"),
+                |value, _| serde_json::json!({ "a": value }),
+            )
+        };
+
+        let within =
+            flatten_json_value(&nested(DEFAULT_MAX_DEPTH)).unwrap();
+        assert_eq!(within.len(), 1);
+        assert!(matches!(
+            flatten_json_value(&nested(DEFAULT_MAX_DEPTH + 1)),
+            Err(MetadataError::ProcessingError { .. })
+        ));
+        assert!(flatten_json_value_with_depth(&nested(3), 2).is_err());
+        assert!(flatten_json_value_with_depth(&nested(3), 3).is_ok());
+    }
+
+    #[test]
+    fn test_inline_list_to_comma_separated() {
+        assert_eq!(
+            inline_list_to_comma_separated("[a, b, c]"),
+            "a, b, c"
+        );
+        assert_eq!(
+            inline_list_to_comma_separated(r#"["x, y", z]"#),
+            "x, y, z"
+        );
+        assert_eq!(inline_list_to_comma_separated("[]"), "");
+        assert_eq!(inline_list_to_comma_separated("a, b"), "a, b");
+    }
+
+    #[test]
+    fn test_flatten_json_value_non_object() {
+        assert!(flatten_json_value(&serde_json::json!(["a"]))
+            .unwrap()
+            .is_empty());
+        assert!(flatten_json_value(&serde_json::json!("a"))
+            .unwrap()
+            .is_empty());
+        assert!(flatten_json_value(&JsonValue::Null)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_standardize_date_errors() {
+        assert!(standardize_date("", &DttDateParser).is_err());
+        assert!(standardize_date("invalid", &DttDateParser).is_err());
+        assert!(standardize_date("20/05/23", &DttDateParser).is_err()); // Invalid DD/MM/YY format
+        for date in
+            ["//////////", "2/05/20231", "a/b/c/d/ef", "20/05/２３"]
+        {
+            assert!(standardize_date(date, &DttDateParser).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_relative_date() {
+        let reference =
+            dtt_parse!("2024-03-01T12:00:00+00:00").unwrap();
+
+        let test_cases = vec![
+            ("today", "2024-03-01"),
+            ("Yesterday", "2024-02-29"),
+            ("tomorrow", "2024-03-02"),
+            ("3 days ago", "2024-02-27"),
+            ("1 day ago", "2024-02-29"),
+            ("  0 days ago  ", "2024-03-01"),
+        ];
+
+        for (input, expected) in test_cases {
+            let result = parse_relative_date(input, &reference);
+            assert!(result.is_ok(), "Failed for input: {}", input);
+            assert_eq!(result.unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_relative_date_errors() {
+        let reference =
+            dtt_parse!("2024-03-01T12:00:00+00:00").unwrap();
+
+        assert!(parse_relative_date("someday", &reference).is_err());
+        assert!(parse_relative_date("3 weeks ago", &reference).is_err());
+        assert!(parse_relative_date("days ago", &reference).is_err());
+        assert!(
+            parse_relative_date("3 days ago now", &reference).is_err()
+        );
+        assert!(parse_relative_date("-3 days ago", &reference).is_err());
+        assert!(parse_relative_date(
+            "-9223372036854775808 days ago",
+            &reference
+        )
+        .is_err());
+        assert!(parse_relative_date("4294967295 days ago", &reference)
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_date_relative_to() {
+        let reference =
+            dtt_parse!("2024-01-01T00:00:00+00:00").unwrap();
+
+        let date =
+            parse_date_relative_to("yesterday", &reference).unwrap();
+        assert_eq!(format_ymd(&date), "2023-12-31");
+
+        let date =
+            parse_date_relative_to("2023-05-20", &reference).unwrap();
+        assert_eq!(format_ymd(&date), "2023-05-20");
+    }
+
+    #[test]
+    fn test_date_format() {
+        let dt = dtt_parse!("2023-01-01T12:00:00+00:00").unwrap();
+        let formatted = format!(
+            "{:04}-{:02}-{:02}",
+            dt.year(),
+            dt.month() as u8,
+            dt.day()
+        );
+        assert_eq!(formatted, "2023-01-01");
+    }
+
+    #[test]
+    fn test_generate_slug() {
+        assert_eq!(generate_slug("Hello World"), "hello-world");
+        assert_eq!(generate_slug("Test 123"), "test-123");
+        assert_eq!(generate_slug("  Spaces  "), "--spaces--");
+    }
+
+    #[test]
+    fn test_process_metadata() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("title".to_string(), "Test Title".to_string());
+        metadata.insert(
+            "date".to_string(),
+            "2023-05-20T15:30:00Z".to_string(),
+        );
+
+        let processed = process_metadata(&metadata).unwrap();
+        assert_eq!(processed.get("title").unwrap(), "Test Title");
+        assert_eq!(processed.get("date").unwrap(), "2023-05-20");
+        assert_eq!(processed.get("slug").unwrap(), "test-title");
+    }
+
+    #[test]
+    fn test_extract_metadata() {
+        let yaml_content = r#"---
+title: YAML Test
+date: 2023-05-20
+---
+Content here"#;
+
+        let toml_content = r#"+++
+title = "TOML Test"
+date = "2023-05-20"
++++
+Content here"#;
+
+        let json_content = r#"{
+"title": "JSON Test",
+"date": "2023-05-20"
+}
+Content here"#;
+
+        let yaml_metadata = extract_metadata(yaml_content).unwrap();
+        assert_eq!(yaml_metadata.get("title").unwrap(), "YAML Test");
+
+        let toml_metadata = extract_metadata(toml_content).unwrap();
+        assert_eq!(toml_metadata.get("title").unwrap(), "TOML Test");
+
+        let json_metadata = extract_metadata(json_content).unwrap();
+        assert_eq!(json_metadata.get("title").unwrap(), "JSON Test");
+    }
+
+    #[test]
+    fn test_extract_metadata_with_custom_delimiters() {
+        let options = ExtractOptions {
+            yaml_delimiters: Delimiters::new("===", "==="),
+            toml_delimiters: Delimiters::new("<<<", ">>>"),
+            json_delimiters: Delimiters::new(";;;", ";;;"),
+            ..ExtractOptions::default()
+        };
+
+        let yaml = "===\ntitle: YAML Custom\n===\nContent";
+        let toml = "<<<\ntitle = \"TOML Custom\"\n>>>\nContent";
+        let json = ";;;\n\"title\": \"JSON Custom\"\n;;;\nContent";
+
+        for (content, expected) in [
+            (yaml, "YAML Custom"),
+            (toml, "TOML Custom"),
+            (json, "JSON Custom"),
+        ] {
+            let metadata =
+                extract_metadata_with_options(content, &options)
+                    .unwrap();
+            assert_eq!(metadata.get("title").unwrap(), expected);
+        }
+
+        // The default delimiters no longer apply.
+        let default_yaml = "---\ntitle: Default\n---\nContent";
+        assert!(extract_metadata_with_options(default_yaml, &options)
+            .is_err());
+    }
+
+    #[test]
+    fn test_extract_metadata_with_empty_delimiters() {
+        let options = ExtractOptions {
+            toml_delimiters: Delimiters::new("+++", "  "),
+            ..ExtractOptions::default()
+        };
+
+        let result = extract_metadata_with_options(
+            "---\ntitle: Test\n---\n",
+            &options,
+        );
+        match result {
+            Err(MetadataError::ValidationError { field, .. }) => {
+                assert_eq!(field, "toml_delimiters");
+            }
+            other => {
+                panic!("Expected ValidationError, got {:?}", other)
+            }
+        }
+    }
+
+    #[test]
+    fn test_extract_metadata_with_bom() {
+        let content =
+            "\u{FEFF}---\ntitle: BOM Test\ndate: 2023-05-20\n---\nContent";
+
+        let metadata = extract_metadata(content).unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "BOM Test");
+        assert_eq!(metadata.get("date").unwrap(), "2023-05-20");
+    }
+
+    #[test]
+    fn test_extract_metadata_with_crlf() {
+        let content =
+            "---\r\ntitle: CRLF Test\r\ndate: 2023-05-20\r\n---\r\nContent";
+
+        let metadata = extract_metadata(content).unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "CRLF Test");
+        assert_eq!(metadata.get("date").unwrap(), "2023-05-20");
+    }
+
+    #[test]
+    fn test_extractors_accept_crlf() {
+        let options = ExtractOptions::default();
+
+        let yaml = "---\r\ntitle: YAML CRLF\r\ndate: 2023-05-20\r\n---\r\nContent";
+        let toml = "+++\r\ntitle = \"TOML CRLF\"\r\n+++\r\nContent";
+        let json = "{\r\n\"title\": \"JSON CRLF\"\r\n}\r\nContent";
+
+        let metadata =
+            extract_yaml_metadata(yaml, &options).unwrap().unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "YAML CRLF");
+        assert_eq!(metadata.get("date").unwrap(), "2023-05-20");
+
+        let metadata =
+            extract_toml_metadata(toml, &options).unwrap().unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "TOML CRLF");
+
+        let metadata =
+            extract_json_metadata(json, &options).unwrap().unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "JSON CRLF");
+
+        for content in [yaml, toml, json] {
+            assert!(extract_metadata(content).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_normalize_content_borrows_when_unchanged() {
+        let content = "---\ntitle: Test\n---\n";
+        assert!(matches!(normalize_content(content), Cow::Borrowed(_)));
+        assert_eq!(normalize_content("\u{FEFF}\r\n---\r\n"), "\n---\n");
+    }
+
+    #[test]
+    fn test_extract_metadata_failure() {
+        let invalid_content = "This content has no metadata";
+        assert!(extract_metadata(invalid_content).is_err());
+    }
+
+    #[test]
+    fn test_ensure_required_fields() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("title".to_string(), "Test".to_string());
+        metadata.insert("date".to_string(), "2023-05-20".to_string());
+
+        assert!(ensure_required_fields(&metadata, false).is_ok());
+
+        let mut incomplete_metadata = Metadata::new(HashMap::new());
+        incomplete_metadata
+            .insert("title".to_string(), "Test".to_string());
+
+        assert!(ensure_required_fields(&incomplete_metadata, false)
+            .is_err());
+    }
+
+    #[test]
+    fn test_generate_derived_fields() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("title".to_string(), "Test Title".to_string());
+
+        generate_derived_fields(
+            &mut metadata,
+            &ProcessOptions::default(),
+        );
+
+        assert_eq!(metadata.get("slug").unwrap(), "test-title");
+    }
+
+    #[test]
+    fn test_generate_slug_with_separators() {
+        assert_eq!(
+            generate_slug_with("Hello World", '_'),
+            "hello_world"
+        );
+        assert_eq!(
+            generate_slug_with("  Multiple   Spaces  ", '_'),
+            "multiple_spaces"
+        );
+        assert_eq!(
+            generate_slug_with("_Already__Separated_ Title", '_'),
+            "already_separated_title"
+        );
+        assert_eq!(
+            generate_slug_with("Hello World", '.'),
+            "hello.world"
+        );
+        assert_eq!(
+            generate_slug_with(". Version  2.0 .", '.'),
+            "version.2.0"
+        );
+        assert_eq!(generate_slug_with("   ", '.'), "");
+    }
+
+    #[test]
+    fn test_extract_rst_metadata() {
+        let content = "\n:title: Sphinx Page
+:author: Jane Doe
+:abstract: A long summary
+    that continues on
+\tindented lines.
+:draft:
+
+Body text
+:not: a field";
+
+        let metadata = extract_rst_metadata(content).unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "Sphinx Page");
+        assert_eq!(metadata.get("author").unwrap(), "Jane Doe");
+        assert_eq!(
+            metadata.get("abstract").unwrap(),
+            "A long summary that continues on indented lines."
+        );
+        assert_eq!(metadata.get("draft").unwrap(), "");
+        assert!(!metadata.contains_key("not"));
+        assert_eq!(metadata.as_map().len(), 4);
+
+        let metadata = extract_metadata(content).unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "Sphinx Page");
+    }
+
+    #[test]
+    fn test_extract_rst_metadata_requires_leading_field_list() {
+        assert!(extract_rst_metadata("Title\n=====\n\n:title: Late")
+            .is_none());
+        assert!(extract_rst_metadata(":not a field").is_none());
+        assert!(extract_rst_metadata(":key:value").is_none());
+        assert!(extract_rst_metadata("").is_none());
+        assert!(extract_metadata("Plain text only").is_err());
+
+        // YAML front matter still takes precedence.
+        let metadata =
+            extract_metadata("---\ntitle: YAML\n---\n:title: RST")
+                .unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "YAML");
+    }
+
+    #[test]
+    fn test_extract_empty_yaml_front_matter() {
+        for content in [
+            "---\n---\n",
+            "---\n---\nBody with key: value",
+            "---\r\n---\r\nBody",
+            "---\n\n---\nBody",
+            "---\n# Only a comment\n---\nBody",
+        ] {
+            let metadata = extract_metadata(content)
+                .unwrap_or_else(|e| panic!("{:?}: {}", content, e));
+            assert!(metadata.as_map().is_empty(), "{:?}", content);
+        }
+    }
+
+    #[test]
+    fn test_extract_empty_toml_front_matter() {
+        for content in
+            ["+++\n+++\n", "+++\n\n+++\nBody", "+++\r\n+++\r\n"]
+        {
+            let metadata = extract_metadata(content)
+                .unwrap_or_else(|e| panic!("{:?}: {}", content, e));
+            assert!(metadata.as_map().is_empty(), "{:?}", content);
+        }
+
+        // No front matter at all is still an error.
+        assert!(matches!(
+            extract_metadata("Just a body"),
+            Err(MetadataError::ExtractionError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_extract_metadata_with_warnings() {
+        let content = "---
+title: First
+draft: true
+tags:
+- rust
+- seo
+settings:
+  published: false
+title: Second
+date: not a date
+---
+Body";
+
+        // Without recovery, the duplicate key makes the YAML invalid.
+        assert!(extract_metadata(content).is_err());
+
+        let (result, warnings) =
+            extract_metadata_with_warnings(content);
+        let metadata = result.unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "Second");
+        assert_eq!(metadata.get("draft").unwrap(), "true");
+        assert_eq!(metadata.get("tags").unwrap(), "[rust, seo]");
+        assert_eq!(
+            metadata.get("settings.published").unwrap(),
+            "false"
+        );
+
+        assert_eq!(warnings.len(), 4, "{:?}", warnings);
+        assert_eq!(
+            warnings[0],
+            "title: duplicate key, last value kept"
+        );
+        assert!(warnings.contains(
+            &"draft: boolean value coerced to string".to_string()
+        ));
+        assert!(warnings.contains(
+            &"settings.published: boolean value coerced to string"
+                .to_string()
+        ));
+        assert!(warnings[3].starts_with("date: `not a date`"));
+    }
+
+    #[test]
+    fn test_extract_metadata_with_warnings_quoted_keys() {
+        let content = "---
+\"a:b\": 1
+\"a:c\": 2
+\"title\": First
+nested:
+  title: Inner
+title: Second
+---
+Body";
+
+        let (result, warnings) =
+            extract_metadata_with_warnings(content);
+        let metadata = result.unwrap();
+        assert_eq!(metadata.get("a:b").unwrap(), "1");
+        assert_eq!(metadata.get("a:c").unwrap(), "2");
+        assert_eq!(metadata.get("title").unwrap(), "Second");
+        assert_eq!(metadata.get("nested.title").unwrap(), "Inner");
+        assert_eq!(
+            warnings,
+            vec!["title: duplicate key, last value kept".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_yaml_keys() {
+        assert_eq!(
+            duplicate_yaml_keys(
+                "a: 1\nb:\n  a: 2\n  a: 3\n? [x]\n: 4\na: 5\nb: 6\na: 7"
+            ),
+            vec!["a".to_string(), "b".to_string()]
+        );
+        assert!(duplicate_yaml_keys("- a\n- a").is_empty());
+        assert!(duplicate_yaml_keys("a: [1, 1]\nb: {a: 1}").is_empty());
+    }
+
+    #[test]
+    fn test_extract_metadata_with_warnings_clean() {
+        let content = "---\ntitle: Clean\ndate: 2023-05-20\n---\nBody";
+        let (result, warnings) =
+            extract_metadata_with_warnings(content);
+        assert_eq!(result.unwrap().get("title").unwrap(), "Clean");
+        assert!(warnings.is_empty());
+
+        let (result, warnings) =
+            extract_metadata_with_warnings("No front matter");
+        assert!(result.is_err());
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_to_toml_front_matter_round_trip() {
+        let content = r#"+++
+title = "Hugo Page"
+tags = ["rust", "static, sites"]
+
+[author]
+name = "Jane Doe"
+email = "jane@example.com"
+
+[params.seo]
+robots = "index"
++++
+Body"#;
+        let mut metadata = extract_metadata(content).unwrap();
+        assert_eq!(metadata.get("author.name").unwrap(), "Jane Doe");
+
+        metadata.insert("title".to_string(), "Edited Page".to_string());
+        metadata.insert("draft".to_string(), "false".to_string());
+
+        metadata.insert("weight".to_string(), "10".to_string());
+        metadata.insert("rating".to_string(), "4.5".to_string());
+        metadata.insert("code".to_string(), "007".to_string());
+        metadata.insert("badge".to_string(), "[WIP]".to_string());
+
+        let front_matter = metadata.to_toml_front_matter().unwrap();
+        assert!(front_matter.starts_with("+++\n"));
+        assert!(front_matter.contains("draft = false\n"));
+        assert!(front_matter.contains("weight = 10\n"));
+        assert!(front_matter.contains("rating = 4.5\n"));
+        assert!(front_matter.contains("code = \"007\"\n"));
+        assert!(front_matter.contains("badge = \"[WIP]\"\n"));
+        assert!(front_matter.ends_with("+++\n"));
+        assert!(front_matter.contains("[author]"));
+        assert!(front_matter.contains("[params.seo]"));
+        assert!(front_matter
+            .contains(r#"tags = ["rust", "static, sites"]"#));
+
+        let rewritten = format!("{}Body", front_matter);
+        let reextracted = extract_metadata(&rewritten).unwrap();
+        assert_eq!(reextracted.sorted_pairs(), metadata.sorted_pairs());
+        assert_eq!(reextracted.get("title").unwrap(), "Edited Page");
+    }
+
+    #[test]
+    fn test_to_toml_front_matter_key_conflict() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("author".to_string(), "Jane".to_string());
+        metadata.insert("author.name".to_string(), "Jane".to_string());
+
+        let error = metadata.to_toml_front_matter().unwrap_err();
+        assert!(matches!(error, MetadataError::TomlError(_)));
+        assert!(error.to_string().contains("author.name"));
+
+        let empty = Metadata::new(HashMap::new());
+        assert_eq!(empty.to_toml_front_matter().unwrap(), "+++\n+++\n");
+    }
+
+    #[test]
+    fn test_metadata_normalize_keys() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("title ".to_string(), "Padded".to_string());
+        metadata.insert(
+            " meta   description\t".to_string(),
+            "Desc".to_string(),
+        );
+        metadata.insert("author".to_string(), "Jane".to_string());
+        metadata.insert("date".to_string(), "old".to_string());
+        metadata.insert(" date".to_string(), "new".to_string());
+
+        assert_eq!(metadata.normalize_keys(), 3);
+        assert_eq!(
+            metadata.sorted_pairs(),
+            vec![
+                ("author".to_string(), "Jane".to_string()),
+                // `date` sorts after ` date`, so its value wins.
+                ("date".to_string(), "old".to_string()),
+                ("meta description".to_string(), "Desc".to_string()),
+                ("title".to_string(), "Padded".to_string()),
+            ]
+        );
+        assert_eq!(metadata.normalize_keys(), 0);
+    }
+
+    #[test]
+    fn test_extract_with_normalize_keys_option() {
+        let content =
+            "---\n\"title \": Padded\n\" tags\": [a, b]\n---\nBody";
+
+        let metadata = extract_metadata(content).unwrap();
+        assert!(metadata.get("title").is_none());
+        assert!(metadata.contains_key("title "));
+
+        let options = ExtractOptions {
+            normalize_keys: true,
+            ..ExtractOptions::default()
+        };
+        let metadata =
+            extract_metadata_with_options(content, &options).unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "Padded");
+        assert_eq!(metadata.get("tags").unwrap(), "[a, b]");
+    }
+
+    #[test]
+    fn test_generate_meta_description_under_limit() {
+        assert_eq!(
+            generate_meta_description(
+                "  A short\n\tdescription. ",
+                160
+            ),
+            "A short description."
+        );
+        assert_eq!(generate_meta_description("", 10), "");
+    }
+
+    #[test]
+    fn test_generate_meta_description_at_limit() {
+        let text = "x".repeat(150) + " ninechars";
+        assert_eq!(text.chars().count(), 160);
+        assert_eq!(generate_meta_description(&text, 160), text);
+        assert_eq!(
+            generate_meta_description(&text, 159),
+            format!("{}…", "x".repeat(150))
+        );
+    }
+
+    #[test]
+    fn test_generate_meta_description_over_limit() {
+        let text = "Rust is a fast, reliable language. ".repeat(10);
+        let description = generate_meta_description(&text, 50);
+        assert_eq!(
+            description,
+            "Rust is a fast, reliable language. Rust is a…"
+        );
+        assert!(description.chars().count() <= 50);
+
+        let description = generate_meta_description(&text, 160);
+        assert!(description.ends_with('…'));
+        assert!(description.chars().count() <= 160);
+
+        assert_eq!(
+            generate_meta_description("Supercalifragilistic", 5),
+            "…"
+        );
+        assert_eq!(generate_meta_description("Long text", 0), "");
+    }
+
+    #[test]
+    fn test_generate_description_from_markdown() {
+        let content = r#"---
+title: A Post
+---
+# A Post
+
+![Cover](/cover.png)
+
+This is the **first** paragraph, with a [link](https://example.com)
+and some `code`, spread over
+two lines.
+
+This is the second paragraph.
+"#;
+
+        assert_eq!(
+            generate_description(content, 160),
+            "This is the first paragraph, with a link and some code, spread over two lines."
+        );
+        assert_eq!(
+            generate_description(content, 32),
+            "This is the first paragraph"
+        );
+        assert_eq!(generate_description(content, 4), "This");
+        assert_eq!(generate_description(content, 2), "Th");
+    }
+
+    #[test]
+    fn test_generate_description_skips_non_prose() {
+        let content = "+++\ntitle = \"T\"\n+++\n```rust\nfn main() {}\n```\n\n---\n\n> - Quoted _list_ item\n";
+        assert_eq!(
+            generate_description(content, 160),
+            "Quoted list item"
+        );
+
+        let json = "{\n  \"title\": \"{Braces}\"\n}\nBody text.";
+        assert_eq!(strip_front_matter(json), "Body text.");
+        assert_eq!(generate_description(json, 160), "Body text.");
+
+        assert_eq!(
+            generate_description("---\ntitle: T\n---\n# Only", 160),
+            ""
+        );
+        assert_eq!(
+            strip_front_matter("---\nunclosed"),
+            "---\nunclosed"
+        );
+    }
+
+    #[test]
+    fn test_extract_json_nested_arrays_of_objects() {
+        let content = r#"{
+  "title": "Team Post",
+  "authors": [{"name": "A", "url": "https://a.example"}, {"name": "B"}],
+  "tags": ["rust", "json"],
+  "seo": {"robots": "index", "weight": 2, "draft": false},
+  "subtitle": null
+}
+Body"#;
+
+        let metadata = extract_metadata(content).unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "Team Post");
+        assert_eq!(metadata.get("authors.0.name").unwrap(), "A");
+        assert_eq!(
+            metadata.get("authors.0.url").unwrap(),
+            "https://a.example"
+        );
+        assert_eq!(metadata.get("authors.1.name").unwrap(), "B");
+        assert!(!metadata.contains_key("authors"));
+        assert_eq!(
+            metadata.get_list("tags").unwrap(),
+            vec!["rust".to_string(), "json".to_string()]
+        );
+        assert_eq!(metadata.get("seo.robots").unwrap(), "index");
+        assert_eq!(metadata.get("seo.weight").unwrap(), "2");
+        assert_eq!(metadata.get("seo.draft").unwrap(), "false");
+        assert!(!metadata.contains_key("subtitle"));
+
+        let parsed = parse_metadata_string(
+            r#"{"authors": [{"name": "A"}, {"name": "B"}]}"#,
+            FrontMatterFormat::Json,
+        )
+        .unwrap();
+        assert_eq!(parsed.get("authors.1.name").unwrap(), "B");
+    }
+
+    #[test]
+    fn test_extract_json_metadata_exceeds_max_depth() {
+        let content = r#"{"a": {"b": {"c": "deep"}}}"#;
+        let options = ExtractOptions {
+            max_depth: 2,
+            ..ExtractOptions::default()
+        };
+        assert!(matches!(
+            extract_metadata_with_options(content, &options),
+            Err(MetadataError::ProcessingError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_metadata_get_non_empty() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("empty".to_string(), String::new());
+        metadata.insert("blank".to_string(), " \t\n".to_string());
+        metadata.insert("title".to_string(), " Title ".to_string());
+
+        assert!(metadata.get_non_empty("empty").is_none());
+        assert!(metadata.get_non_empty("blank").is_none());
+        assert!(metadata.get_non_empty("missing").is_none());
+        assert_eq!(metadata.get_non_empty("title").unwrap(), " Title ");
+    }
+
+    #[test]
+    fn test_process_metadata_date_on_error_fail() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("title".to_string(), "Title".to_string());
+        metadata
+            .insert("date".to_string(), "next blue moon".to_string());
+
+        let options = ProcessOptions {
+            date_on_error: DateErrorPolicy::Fail,
+            ..ProcessOptions::default()
+        };
+        assert_eq!(
+            ProcessOptions::default().date_on_error,
+            options.date_on_error
+        );
+        assert!(matches!(
+            process_metadata_with_options(&metadata, &options),
+            Err(MetadataError::DateParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_process_metadata_date_on_error_keep() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("title".to_string(), "Title".to_string());
+        metadata
+            .insert("date".to_string(), "next blue moon".to_string());
+
+        let options = ProcessOptions {
+            date_on_error: DateErrorPolicy::Keep,
+            ..ProcessOptions::default()
+        };
+        let processed =
+            process_metadata_with_options(&metadata, &options).unwrap();
+        assert_eq!(processed.get("date").unwrap(), "next blue moon");
+        assert_eq!(processed.get("slug").unwrap(), "title");
+    }
+
+    #[test]
+    fn test_process_metadata_date_on_error_drop() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("title".to_string(), "Title".to_string());
+        metadata
+            .insert("date".to_string(), "next blue moon".to_string());
+
+        let options = ProcessOptions {
+            date_on_error: DateErrorPolicy::Drop,
+            ..ProcessOptions::default()
+        };
+        let processed =
+            process_metadata_with_options(&metadata, &options).unwrap();
+        assert!(!processed.contains_key("date"));
+        assert_eq!(processed.get("slug").unwrap(), "title");
+
+        // A valid date is still standardized.
+        metadata.insert("date".to_string(), "20/05/2023".to_string());
+        let processed =
+            process_metadata_with_options(&metadata, &options).unwrap();
+        assert_eq!(processed.get("date").unwrap(), "2023-05-20");
+    }
+
+    #[test]
+    fn test_process_metadata_treat_empty_as_missing() {
+        let options = ProcessOptions {
+            treat_empty_as_missing: true,
+            ..ProcessOptions::default()
+        };
+
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("title".to_string(), "   ".to_string());
+        metadata.insert("date".to_string(), "2023-05-20".to_string());
+        // By default, a present but blank title is accepted.
+        assert!(process_metadata(&metadata).is_ok());
+        assert!(matches!(
+            process_metadata_with_options(&metadata, &options),
+            Err(MetadataError::MissingFieldError(field)) if field == "title"
+        ));
+
+        metadata.insert("title".to_string(), "Title".to_string());
+        metadata.insert("date".to_string(), String::new());
+        assert!(matches!(
+            process_metadata(&metadata),
+            Err(MetadataError::DateParseError(_))
+        ));
+        assert!(matches!(
+            process_metadata_with_options(&metadata, &options),
+            Err(MetadataError::MissingFieldError(field)) if field == "date"
+        ));
+
+        metadata.insert("date".to_string(), "2023-05-20".to_string());
+        assert!(
+            process_metadata_with_options(&metadata, &options).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_metadata_get_or() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("layout".to_string(), "post".to_string());
+        metadata.insert("subtitle".to_string(), String::new());
+
+        assert_eq!(metadata.get_or("layout", "page"), "post");
+        assert_eq!(metadata.get_or("theme", "light"), "light");
+        // Present but empty values are returned as they are.
+        assert_eq!(metadata.get_or("subtitle", "none"), "");
+
+        assert_eq!(
+            metadata.get_owned_or("layout", "page".to_string()),
+            "post"
+        );
+        assert_eq!(
+            metadata.get_owned_or("theme", "light".to_string()),
+            "light"
+        );
+    }
+
+    #[test]
+    fn test_metadata_retain_whitelist() {
+        let mut metadata = Metadata::new(HashMap::new());
+        for (key, value) in [
+            ("title", "Title"),
+            ("description", "A page"),
+            ("keywords", "rust"),
+            ("layout", "post"),
+            ("permalink", "/post/"),
+        ] {
+            metadata.insert(key.to_string(), value.to_string());
+        }
+
+        let whitelist = ["title", "description", "keywords"];
+        metadata.retain(|key, _| whitelist.contains(&key));
+        assert_eq!(
+            metadata.sorted_pairs(),
+            vec![
+                ("description".to_string(), "A page".to_string()),
+                ("keywords".to_string(), "rust".to_string()),
+                ("title".to_string(), "Title".to_string()),
+            ]
+        );
+
+        metadata.retain(|_, value| value != "rust");
+        assert!(!metadata.contains_key("keywords"));
+    }
+
+    #[test]
+    fn test_metadata_content_hash() {
+        let pairs = [
+            ("title", "Hello"),
+            ("lang", "en"),
+            ("date", "2023-05-20"),
+        ];
+
+        let mut forward = Metadata::new(HashMap::new());
+        for (key, value) in pairs {
+            forward.insert(key.to_string(), value.to_string());
+        }
+        let mut backward = Metadata::new(HashMap::new());
+        for (key, value) in pairs.iter().rev() {
+            backward.insert(key.to_string(), value.to_string());
+        }
+
+        let hash = forward.content_hash();
+        assert_eq!(hash, backward.content_hash());
+        assert_eq!(hash.len(), 16);
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+        // The hash is stable across runs and platforms.
+        assert_eq!(
+            Metadata::new(HashMap::new()).content_hash(),
+            "cbf29ce484222325"
+        );
+
+        backward.insert("title".to_string(), "Hello!".to_string());
+        assert_ne!(hash, backward.content_hash());
+    }
+
+    #[test]
+    fn test_metadata_content_hash_boundaries() {
+        let mut a = Metadata::new(HashMap::new());
+        a.insert("ab".to_string(), "c".to_string());
+        let mut b = Metadata::new(HashMap::new());
+        b.insert("a".to_string(), "bc".to_string());
+        assert_ne!(a.content_hash(), b.content_hash());
+    }
+
+    #[test]
+    fn test_metadata_sorted_pairs() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("title".to_string(), "Title".to_string());
+        metadata.insert("author".to_string(), "Jane".to_string());
+        metadata.insert("date".to_string(), "2023-05-20".to_string());
+
+        assert_eq!(
+            metadata.sorted_pairs(),
+            vec![
+                ("author".to_string(), "Jane".to_string()),
+                ("date".to_string(), "2023-05-20".to_string()),
+                ("title".to_string(), "Title".to_string()),
+            ]
+        );
+        assert!(Metadata::new(HashMap::new())
+            .sorted_pairs()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_dedupe_slug() {
+        let mut existing = HashSet::new();
+        assert_eq!(dedupe_slug("post", &mut existing), "post");
+        assert_eq!(dedupe_slug("post", &mut existing), "post-2");
+        assert_eq!(dedupe_slug("post", &mut existing), "post-3");
+        assert_eq!(dedupe_slug("other", &mut existing), "other");
+        assert_eq!(existing.len(), 4);
+
+        // A slug that already ends in a suffix is handled like any other.
+        assert_eq!(dedupe_slug("post-2", &mut existing), "post-2-2");
+
+        let mut existing: HashSet<String> =
+            ["page", "page-2", "page-3"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+        assert_eq!(dedupe_slug("page", &mut existing), "page-4");
+        assert!(existing.contains("page-4"));
+    }
+
+    #[test]
+    fn test_generate_slug_with_options_unicode() {
+        let options = SlugOptions::default();
+        assert_eq!(
+            generate_slug_with_options("Привет, Мир!", &options),
+            "привет-мир"
+        );
+        assert_eq!(
+            generate_slug_with_options("東京 «旅行»：ガイド", &options),
+            "東京-旅行ガイド"
+        );
+        assert_eq!(
+            generate_slug_with_options(
+                "Crème Brûlée — a recipe?",
+                &options
+            ),
+            "crème-brûlée-a-recipe"
+        );
+        assert_eq!(
+            generate_slug_with_options("Rust & C++ / 2024", &options),
+            "rust-c-2024"
+        );
+    }
+
+    #[test]
+    fn test_generate_slug_with_options_ascii_only() {
+        let options = SlugOptions {
+            separator: '_',
+            ascii_only: true,
+        };
+        assert_eq!(
+            generate_slug_with_options(
+                "Crème Brûlée à la Française",
+                &options
+            ),
+            "creme_brulee_a_la_francaise"
+        );
+        assert_eq!(
+            generate_slug_with_options("Straße -- Ærø", &options),
+            "strasse_aero"
+        );
+        assert_eq!(
+            generate_slug_with_options("Привет мир", &options),
+            "privet_mir"
+        );
+    }
+
+    #[test]
+    fn test_process_metadata_with_slug_ascii_only() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata
+            .insert("title".to_string(), "Café Déjà Vu".to_string());
+        metadata.insert("date".to_string(), "2023-05-20".to_string());
+
+        let options = ProcessOptions {
+            slug_ascii_only: true,
+            ..ProcessOptions::default()
+        };
+        let processed =
+            process_metadata_with_options(&metadata, &options).unwrap();
+        assert_eq!(processed.get("slug").unwrap(), "cafe-deja-vu");
+    }
+
+    #[test]
+    fn test_process_metadata_with_slug_separator() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata
+            .insert("title".to_string(), " Test  Title ".to_string());
+        metadata.insert("date".to_string(), "2023-05-20".to_string());
+
+        let options = ProcessOptions {
+            slug_separator: Some('_'),
+            ..ProcessOptions::default()
+        };
+        let processed =
+            process_metadata_with_options(&metadata, &options).unwrap();
+        assert_eq!(processed.get("slug").unwrap(), "test_title");
+
+        let processed = process_metadata(&metadata).unwrap();
+        assert_eq!(processed.get("slug").unwrap(), "-test--title-");
+
+        let options = ProcessOptions {
+            slug_separator: Some('-'),
+            ..ProcessOptions::default()
+        };
+        let processed =
+            process_metadata_with_options(&metadata, &options).unwrap();
+        assert_eq!(processed.get("slug").unwrap(), "test-title");
+    }
+
+    #[test]
+    fn test_metadata_methods() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("key".to_string(), "value".to_string());
+
+        assert_eq!(metadata.get("key"), Some(&"value".to_string()));
+        assert!(metadata.contains_key("key"));
+        assert!(!metadata.contains_key("nonexistent"));
+
+        let old_value =
+            metadata.insert("key".to_string(), "new_value".to_string());
+        assert_eq!(old_value, Some("value".to_string()));
+        assert_eq!(metadata.get("key"), Some(&"new_value".to_string()));
+
+        let inner = metadata.into_inner();
+        assert_eq!(inner.get("key"), Some(&"new_value".to_string()));
+    }
+
+    /// Parses dates written as `DAY.MONTH.YEAR`, e.g. `20.5.2023`.
+    struct DottedDateParser;
+
+    impl DateParser for DottedDateParser {
+        fn parse_to_ymd(
+            &self,
+            input: &str,
+        ) -> Result<(i32, u8, u8), MetadataError> {
+            let invalid = || {
+                MetadataError::DateParseError(format!(
+                    "Not a dotted date: {}",
+                    input
+                ))
+            };
+            let mut parts = input.split('.');
+            let day = parts.next().and_then(|d| d.parse().ok());
+            let month = parts.next().and_then(|m| m.parse().ok());
+            let year = parts.next().and_then(|y| y.parse().ok());
+            match (year, month, day, parts.next()) {
+                (Some(y), Some(m), Some(d), None) => Ok((y, m, d)),
+                _ => Err(invalid()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_process_metadata_with_custom_date_parser() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("title".to_string(), "Test Title".to_string());
+        metadata.insert("date".to_string(), "20.5.2023".to_string());
+
+        // The default parser does not understand the bespoke format.
+        assert!(process_metadata(&metadata).is_err());
+
+        let options = ProcessOptions {
+            date_parser: &DottedDateParser,
+            ..ProcessOptions::default()
+        };
+        let processed =
+            process_metadata_with_options(&metadata, &options).unwrap();
+        assert_eq!(processed.get("date").unwrap(), "2023-05-20");
+        assert_eq!(processed.get("slug").unwrap(), "test-title");
+    }
+
+    #[test]
+    fn test_process_metadata_rejects_out_of_range_parts() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("title".to_string(), "Test Title".to_string());
+        metadata.insert("date".to_string(), "20.13.2023".to_string());
+
+        let options = ProcessOptions {
+            date_parser: &DottedDateParser,
+            ..ProcessOptions::default()
+        };
+        assert!(matches!(
+            process_metadata_with_options(&metadata, &options),
+            Err(MetadataError::DateParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_dtt_date_parser() {
+        assert_eq!(
+            DttDateParser.parse_to_ymd("2023-05-20T15:30:00Z").unwrap(),
+            (2023, 5, 20)
+        );
+        assert!(DttDateParser.parse_to_ymd("invalid").is_err());
+    }
+
+    #[test]
+    fn test_clear_derived() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("title".to_string(), "Test Title".to_string());
+        metadata.insert("date".to_string(), "2023-05-20".to_string());
+        metadata.insert("slug".to_string(), "old-slug".to_string());
+        metadata.insert("word_count".to_string(), "42".to_string());
+        metadata.insert("reading_time".to_string(), "1".to_string());
+
+        metadata.clear_derived(&DEFAULT_DERIVED_KEYS);
+
+        assert!(!metadata.contains_key("slug"));
+        assert!(!metadata.contains_key("word_count"));
+        assert!(!metadata.contains_key("reading_time"));
+        assert_eq!(metadata.get("title").unwrap(), "Test Title");
+        assert_eq!(metadata.get("date").unwrap(), "2023-05-20");
+
+        let processed = process_metadata(&metadata).unwrap();
+        assert_eq!(processed.get("slug").unwrap(), "test-title");
+    }
+
+    #[test]
+    fn test_clear_derived_custom_keys() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("slug".to_string(), "kept".to_string());
+        metadata.insert("summary".to_string(), "removed".to_string());
+
+        metadata.clear_derived(&["summary", "missing"]);
+
+        assert!(metadata.contains_key("slug"));
+        assert!(!metadata.contains_key("summary"));
+    }
+
+    #[test]
+    fn test_apply_defaults() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("title".to_string(), "Post".to_string());
+        metadata.insert("author".to_string(), "Jane".to_string());
+        metadata.insert("site_name".to_string(), String::new());
+
+        let defaults = [
+            ("author", "Site Team"),
+            ("site_name", "My Site"),
+            ("og:type", "website"),
+            ("lang", "en"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect::<HashMap<_, _>>();
+
+        metadata.apply_defaults(&defaults);
+        assert_eq!(metadata.as_map().len(), 5);
+        assert_eq!(metadata.get("title").unwrap(), "Post");
+        assert_eq!(metadata.get("author").unwrap(), "Jane");
+        assert_eq!(metadata.get("site_name").unwrap(), "");
+        assert_eq!(metadata.get("og:type").unwrap(), "website");
+        assert_eq!(metadata.get("lang").unwrap(), "en");
+    }
+
+    #[test]
+    fn test_apply_aliases() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("title".to_string(), "Test".to_string());
+        metadata.insert("summary".to_string(), "A summary".to_string());
+        metadata.insert("tags".to_string(), "[rust, seo]".to_string());
+
+        let mut aliases = default_aliases();
+        aliases.insert("headline".to_string(), "title".to_string());
+        metadata.apply_aliases(&aliases);
+
+        assert_eq!(metadata.get("description").unwrap(), "A summary");
+        assert_eq!(metadata.get("keywords").unwrap(), "[rust, seo]");
+        assert_eq!(metadata.get("title").unwrap(), "Test");
+        assert!(!metadata.contains_key("summary"));
+        assert!(!metadata.contains_key("tags"));
+    }
+
+    #[test]
+    fn test_apply_aliases_last_writer_wins() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata
+            .insert("description".to_string(), "Original".to_string());
+        metadata.insert("desc".to_string(), "Short".to_string());
+        metadata.insert("summary".to_string(), "Summary".to_string());
+
+        metadata.apply_aliases(&default_aliases());
+
+        assert_eq!(metadata.get("description").unwrap(), "Summary");
+        assert!(!metadata.contains_key("desc"));
+        assert!(!metadata.contains_key("summary"));
+    }
+
+    #[test]
+    fn test_process_metadata_in_place_matches_process_metadata() {
+        let inputs: Vec<Metadata> = (0..200)
+            .map(|i| {
+                let mut metadata = Metadata::new(HashMap::new());
+                metadata
+                    .insert("title".to_string(), format!("Post {}", i));
+                metadata.insert(
+                    "date".to_string(),
+                    format!("{:02}/05/2023", i % 28 + 1),
+                );
+                for field in 0..20 {
+                    metadata.insert(
+                        format!("custom_{}", field),
+                        "value".to_string(),
+                    );
+                }
+                metadata
+            })
+            .collect();
+
+        for input in inputs {
+            let expected = process_metadata(&input).unwrap();
+            let mut in_place = input;
+            process_metadata_in_place(&mut in_place).unwrap();
+            assert_eq!(in_place.into_inner(), expected.into_inner());
+        }
+    }
+
+    #[test]
+    fn test_process_metadata_in_place_unchanged_on_error() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("date".to_string(), "20/05/2023".to_string());
+        let original = metadata.as_map().clone();
+
+        assert!(matches!(
+            process_metadata_in_place(&mut metadata),
+            Err(MetadataError::MissingFieldError(_))
+        ));
+        assert_eq!(metadata.into_inner(), original);
+    }
+
+    #[test]
+    fn test_as_map() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("title".to_string(), "Test".to_string());
+
+        let map = metadata.as_map();
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get("title"), Some(&"Test".to_string()));
+        assert_eq!(metadata.get("title").unwrap(), "Test");
+    }
+
+    fn rule(
+        required: bool,
+        max_len: Option<usize>,
+        kind: FieldKind,
+    ) -> FieldRule {
+        FieldRule {
+            required,
+            max_len,
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_validate_sitemap_fields() {
+        let mut metadata = Metadata::new(HashMap::new());
+        assert!(validate_sitemap_fields(&metadata).is_ok());
+
+        for changefreq in SITEMAP_CHANGE_FREQUENCIES {
+            metadata.insert(
+                "changefreq".to_string(),
+                changefreq.to_string(),
+            );
+            assert!(validate_sitemap_fields(&metadata).is_ok());
+        }
+        for priority in ["0", "0.0", "0.5", "1.0", " 1 "] {
+            metadata
+                .insert("priority".to_string(), priority.to_string());
+            assert!(validate_sitemap_fields(&metadata).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_validate_sitemap_fields_invalid_changefreq() {
+        for changefreq in ["sometimes", "Weekly", "", "fortnightly"] {
+            let mut metadata = Metadata::new(HashMap::new());
+            metadata.insert(
+                "changefreq".to_string(),
+                changefreq.to_string(),
+            );
+            match validate_sitemap_fields(&metadata) {
+                Err(MetadataError::ValidationError {
+                    field, ..
+                }) => {
+                    assert_eq!(field, "changefreq");
+                }
+                other => {
+                    panic!("Expected ValidationError, got {:?}", other)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_sitemap_fields_invalid_priority() {
+        for priority in ["1.5", "-0.1", "high", "NaN", "inf"] {
+            let mut metadata = Metadata::new(HashMap::new());
+            metadata
+                .insert("changefreq".to_string(), "daily".to_string());
+            metadata
+                .insert("priority".to_string(), priority.to_string());
+            match validate_sitemap_fields(&metadata) {
+                Err(MetadataError::ValidationError {
+                    field, ..
+                }) => {
+                    assert_eq!(field, "priority");
+                }
+                other => {
+                    panic!("Expected ValidationError, got {:?}", other)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_with_rules_valid() {
+        let mut rules = HashMap::new();
+        rules.insert(
+            "title".to_string(),
+            rule(true, Some(70), FieldKind::Text),
+        );
+        rules.insert(
+            "date".to_string(),
+            rule(true, None, FieldKind::Date),
+        );
+        rules.insert(
+            "url".to_string(),
+            rule(false, None, FieldKind::Url),
+        );
+        rules.insert(
+            "email".to_string(),
+            rule(false, None, FieldKind::Email),
+        );
+        rules.insert(
+            "description".to_string(),
+            rule(false, Some(160), FieldKind::Text),
+        );
+
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("title".to_string(), "Hello".to_string());
+        metadata.insert("date".to_string(), "2023-05-20".to_string());
+        metadata.insert(
+            "url".to_string(),
+            "https://example.com/post".to_string(),
+        );
+        metadata.insert(
+            "email".to_string(),
+            "jane@example.com".to_string(),
+        );
+
+        assert!(validate_with_rules(&metadata, &rules).is_ok());
+    }
+
+    #[test]
+    fn test_validate_with_rules_collects_all_failures() {
+        let mut rules = HashMap::new();
+        rules.insert(
+            "title".to_string(),
+            rule(true, Some(5), FieldKind::Text),
+        );
+        rules.insert(
+            "date".to_string(),
+            rule(true, None, FieldKind::Date),
+        );
+        rules.insert(
+            "author".to_string(),
+            rule(true, None, FieldKind::Text),
+        );
+        rules.insert(
+            "url".to_string(),
+            rule(false, None, FieldKind::Url),
+        );
+        rules.insert(
+            "email".to_string(),
+            rule(false, None, FieldKind::Email),
+        );
+
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("title".to_string(), "Too long".to_string());
+        metadata.insert("date".to_string(), "not a date".to_string());
+        metadata.insert("url".to_string(), "example.com".to_string());
+        metadata.insert("email".to_string(), "jane@".to_string());
+
+        let errors =
+            validate_with_rules(&metadata, &rules).unwrap_err();
+        let fields: Vec<String> = errors
+            .iter()
+            .map(|error| match error {
+                MetadataError::MissingFieldError(field) => {
+                    field.clone()
+                }
+                MetadataError::ValidationError { field, .. } => {
+                    field.clone()
+                }
+                other => panic!("Unexpected error: {}", other),
+            })
+            .collect();
+        assert_eq!(
+            fields,
+            vec!["author", "date", "email", "title", "url"]
+        );
+        assert!(errors[0].is_missing_field());
+    }
+
+    #[test]
+    fn test_partition_known() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("title".to_string(), "Test".to_string());
+        metadata.insert("date".to_string(), "2023-05-20".to_string());
+        metadata.insert("hero_color".to_string(), "teal".to_string());
+        metadata.insert("layout".to_string(), "wide".to_string());
+
+        let (known, extra) =
+            metadata.partition_known(&["title", "date", "description"]);
+
+        assert_eq!(known.len(), 2);
+        assert_eq!(known.get("title"), Some(&"Test".to_string()));
+        assert_eq!(known.get("date"), Some(&"2023-05-20".to_string()));
+        assert_eq!(extra.len(), 2);
+        assert_eq!(extra.get("hero_color"), Some(&"teal".to_string()));
+        assert_eq!(extra.get("layout"), Some(&"wide".to_string()));
+    }
+
+    #[test]
+    fn test_process_metadata_with_invalid_date() {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert("title".to_string(), "Test Title".to_string());
+        metadata.insert("date".to_string(), "invalid_date".to_string());
+
+        assert!(process_metadata(&metadata).is_err());
+    }
+
+    #[test]
+    fn test_extract_yaml_metadata_with_complex_structure() {
+        let yaml_content = r#"---
+title: Complex YAML Test
+date: 2023-05-20
+author:
+  name: John Doe
+  email: john@example.com
+tags:
+  - rust
+  - metadata
+  - testing
+---
+Content here"#;
+
+        let metadata = extract_metadata(yaml_content).unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "Complex YAML Test");
+        assert_eq!(metadata.get("date").unwrap(), "2023-05-20");
+        assert_eq!(metadata.get("author.name").unwrap(), "John Doe");
+        assert_eq!(
+            metadata.get("author.email").unwrap(),
+            "john@example.com"
+        );
+        assert_eq!(
+            metadata.get("tags").unwrap(),
+            "[rust, metadata, testing]"
+        );
+    }
+
+    #[test]
+    fn test_extract_toml_metadata_with_complex_structure() {
+        let toml_content = r#"+++
+title = "Complex TOML Test"
+date = 2023-05-20
+
+[author]
+name = "John Doe"
+email = "john@example.com"
+
+tags = ["rust", "metadata", "testing"]
++++
+Content here"#;
+
+        let metadata = extract_metadata(toml_content).unwrap();
+        assert_eq!(
+            metadata.get("title").expect("Missing 'title' key"),
+            "Complex TOML Test"
+        );
+        assert_eq!(
+            metadata.get("date").expect("Missing 'date' key"),
+            "2023-05-20"
+        );
+        assert_eq!(
+            metadata
+                .get("author.name")
+                .expect("Missing 'author.name' key"),
+            "John Doe"
+        );
+        assert_eq!(
+            metadata
+                .get("author.email")
+                .expect("Missing 'author.email' key"),
+            "john@example.com"
+        );
+        assert_eq!(
+            metadata
+                .get("author.tags")
+                .expect("Missing 'author.tags' key"),
+            "[rust, metadata, testing]"
+        );
+    }
+
+    /// Builds YAML front matter with `depth` levels of nested mappings.
+    fn nested_yaml(depth: usize) -> String {
+        let mut yaml = String::from("---\n");
+        for level in 0..depth {
+            yaml.push_str(&"  ".repeat(level));
+            yaml.push_str(&format!("level{}:\n", level));
+        }
+        yaml.push_str(&"  ".repeat(depth));
+        yaml.push_str("leaf: value\n---\nContent here");
+        yaml
+    }
+
+    #[test]
+    fn test_extract_yaml_metadata_exceeds_max_depth() {
+        let content = nested_yaml(DEFAULT_MAX_DEPTH + 8);
+
+        let result = extract_metadata(&content);
+        assert!(matches!(
+            result,
+            Err(MetadataError::ProcessingError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_extract_yaml_metadata_within_max_depth() {
+        let content = nested_yaml(3);
+
+        let metadata = extract_metadata(&content).unwrap();
+        assert_eq!(
+            metadata.get("level0.level1.level2.leaf").unwrap(),
+            "value"
+        );
+    }
+
+    #[test]
+    fn test_extract_metadata_with_custom_max_depth() {
+        let content = nested_yaml(3);
+
+        let strict = ExtractOptions {
+            max_depth: 3,
+            ..ExtractOptions::default()
+        };
+        assert!(matches!(
+            extract_metadata_with_options(&content, &strict),
+            Err(MetadataError::ProcessingError { .. })
+        ));
+
+        let relaxed = ExtractOptions {
+            max_depth: 4,
+            ..ExtractOptions::default()
+        };
+        assert!(
+            extract_metadata_with_options(&content, &relaxed).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_extract_toml_metadata_exceeds_max_depth() {
+        let content = "+++\n[a.b.c]\nd = 1\n+++\nContent here";
+
+        let options = ExtractOptions {
+            max_depth: 2,
+            ..ExtractOptions::default()
+        };
+        assert!(matches!(
+            extract_metadata_with_options(content, &options),
+            Err(MetadataError::ProcessingError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_extract_toml_metadata_with_delimiter_in_content() {
+        let toml_content = r#"+++
+title = "C+++ tricks"
+description = "Uses +++ inline"
++++
+Body text with a +++ in it.
++++
+More body."#;
+
+        let metadata = extract_metadata(toml_content).unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "C+++ tricks");
+        assert_eq!(
+            metadata.get("description").unwrap(),
+            "Uses +++ inline"
+        );
+    }
+
+    #[test]
+    fn test_extract_toml_metadata_with_toml_fence() {
+        let content = "---toml\ntitle = \"Fenced\"\n[author]\nname = \"Jane\"\n---\nBody\n---\nMore";
+
+        let metadata = extract_metadata(content).unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "Fenced");
+        assert_eq!(metadata.get("author.name").unwrap(), "Jane");
+        assert_eq!(
+            has_front_matter(content),
+            Some(FrontMatterFormat::Toml)
+        );
+        assert_eq!(strip_front_matter(content), "Body\n---\nMore");
+
+        let crlf = "---toml \r\ntitle = \"CRLF\"\r\n---\r\nBody";
+        let metadata = extract_metadata(crlf).unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "CRLF");
+
+        // A `+++` line does not close a `---toml` block.
+        let mixed = "---toml\ntitle = \"Mixed\"\n+++\nBody";
+        assert!(extract_toml_metadata(
+            mixed,
+            &ExtractOptions::default()
+        )
+        .unwrap()
+        .is_none());
+    }
+
+    #[test]
+    fn test_extract_toml_metadata_plus_fence_unaffected() {
+        let content = "+++\ntitle = \"Plus\"\n+++\nBody\n---\nMore";
+
+        let metadata = extract_metadata(content).unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "Plus");
+        assert_eq!(
+            has_front_matter(content),
+            Some(FrontMatterFormat::Toml)
+        );
+        assert_eq!(strip_front_matter(content), "Body\n---\nMore");
+
+        // YAML front matter is still read as YAML.
+        let yaml = "---\ntitle: YAML\n---\nBody";
+        assert_eq!(
+            extract_metadata(yaml).unwrap().get("title").unwrap(),
+            "YAML"
+        );
+    }
+
+    #[test]
+    fn test_extract_toml_metadata_delimiters_on_own_lines() {
+        let options = ExtractOptions::default();
+
+        let spaced = "+++ \t\ntitle = \"Spaced\"\n\t+++  \nBody";
+        let metadata =
+            extract_toml_metadata(spaced, &options).unwrap().unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "Spaced");
+
+        let empty = "+++\n+++\nBody";
+        let metadata =
+            extract_toml_metadata(empty, &options).unwrap().unwrap();
+        assert!(metadata.into_inner().is_empty());
+
+        let same_line = "+++ title = \"Inline\" +++\nBody";
+        assert!(extract_toml_metadata(same_line, &options)
+            .unwrap()
+            .is_none());
+
+        let trailing = "+++\ntitle = \"Trailing\"\n+++ more text\nBody";
+        assert!(extract_toml_metadata(trailing, &options)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_extract_metadata_exceeds_max_front_matter_bytes() {
+        let yaml = format!(
+            "---\ntitle: Large\nbody: {}\n---\nContent",
+            "x".repeat(256)
+        );
+        let toml = format!(
+            "+++\ntitle = \"Large\"\nbody = \"{}\"\n+++\nContent",
+            "x".repeat(256)
+        );
+        let json = format!(
+            "{{\"title\": \"Large\", \"body\": \"{}\"}}\nContent",
+            "x".repeat(256)
+        );
+
+        let options = ExtractOptions {
+            max_front_matter_bytes: Some(128),
+            ..ExtractOptions::default()
+        };
+        for content in [&yaml, &toml, &json] {
+            assert!(matches!(
+                extract_metadata_with_options(content, &options),
+                Err(MetadataError::ExtractionError { .. })
+            ));
+            assert!(extract_metadata(content).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_extract_rst_metadata_max_front_matter_bytes() {
+        let block =
+            ":title: Sphinx Page\n:abstract: A long\n   abstract.\n";
+        let content =
+            format!("\n{}\nBody text that is not counted.\n", block);
+
+        let within = ExtractOptions {
+            max_front_matter_bytes: Some(block.len()),
+            ..ExtractOptions::default()
+        };
+        let metadata =
+            extract_metadata_with_options(&content, &within).unwrap();
+        assert_eq!(
+            metadata.get("abstract").unwrap(),
+            "A long abstract."
+        );
+
+        let exceeded = ExtractOptions {
+            max_front_matter_bytes: Some(block.len() - 1),
+            ..ExtractOptions::default()
+        };
+        assert!(matches!(
+            extract_metadata_with_options(&content, &exceeded),
+            Err(MetadataError::ExtractionError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_extract_metadata_within_max_front_matter_bytes() {
+        let content = "---\ntitle: Small\n---\nContent";
+        let options = ExtractOptions {
+            max_front_matter_bytes: Some("title: Small".len()),
+            ..ExtractOptions::default()
+        };
+
+        let metadata =
+            extract_metadata_with_options(content, &options).unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "Small");
+    }
+
+    #[cfg(feature = "json5")]
+    #[test]
+    fn test_extract_json5_metadata() {
+        let content = r#"{
+    // A line comment
+    title: "JSON5 Page",
+    /* A block comment */
+    'description': 'Single quotes',
+    tags: ["a", "b",],
+}
+Content here"#;
+
+        let metadata = extract_metadata(content).unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "JSON5 Page");
+        assert_eq!(
+            metadata.get("description").unwrap(),
+            "Single quotes"
+        );
+    }
+
+    #[cfg(not(feature = "json5"))]
+    #[test]
+    fn test_extract_json5_metadata_requires_feature() {
+        let content = "{ title: \"JSON5 Page\", }\nContent here";
+        assert!(extract_metadata(content).is_err());
+    }
+
+    #[test]
+    fn test_extract_metadata_bytes() {
+        let content = "---\ntitle: Café\n---\nBody".as_bytes();
+        let metadata = extract_metadata_bytes(content).unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "Café");
+    }
+
+    #[test]
+    fn test_extract_metadata_bytes_invalid_utf8() {
+        let content = b"---\ntitle: \xFF\xFE\n---\nBody";
+        assert!(matches!(
+            extract_metadata_bytes(content),
+            Err(MetadataError::Utf8Error(_))
+        ));
+    }
+
+    #[test]
+    fn test_has_front_matter() {
+        assert_eq!(
+            has_front_matter("---\ntitle: YAML\n---\nBody"),
+            Some(FrontMatterFormat::Yaml)
+        );
+        assert_eq!(
+            has_front_matter(
+                "\u{FEFF}\n+++\r\ntitle = \"TOML\"\r\n+++\r\n"
+            ),
+            Some(FrontMatterFormat::Toml)
+        );
+        assert_eq!(
+            has_front_matter("{\"title\": \"JSON\"}\nBody"),
+            Some(FrontMatterFormat::Json)
+        );
+        assert_eq!(has_front_matter("# Heading\n\nPlain text."), None);
+        assert_eq!(has_front_matter("--- not a fence\n"), None);
+        assert_eq!(has_front_matter(""), None);
+    }
+
+    #[test]
+    fn test_front_matter_span() {
+        let yaml = "\u{FEFF}\n---\ntitle: YAML\n---  \nBody";
+        let (format, span) = front_matter_span(yaml).unwrap();
+        assert_eq!(format, FrontMatterFormat::Yaml);
+        assert_eq!(&yaml[span.clone()], "---\ntitle: YAML\n---");
+        assert_eq!(span.start, "\u{FEFF}\n".len());
+
+        let toml = "+++\r\ntitle = \"TOML\"\r\n+++\r\nBody";
+        let (format, span) = front_matter_span(toml).unwrap();
+        assert_eq!(format, FrontMatterFormat::Toml);
+        assert_eq!(&toml[span], "+++\r\ntitle = \"TOML\"\r\n+++");
+
+        let fenced = "---toml\ntitle = \"TOML\"\n---\nBody";
+        let (format, span) = front_matter_span(fenced).unwrap();
+        assert_eq!(format, FrontMatterFormat::Toml);
+        assert_eq!(&fenced[span], "---toml\ntitle = \"TOML\"\n---");
+
+        let json = "{\"title\": \"{JSON}\"}\nBody";
+        let (format, span) = front_matter_span(json).unwrap();
+        assert_eq!(format, FrontMatterFormat::Json);
+        assert_eq!(&json[span], "{\"title\": \"{JSON}\"}");
+    }
+
+    #[test]
+    fn test_front_matter_span_in_place_edit() {
+        let content = "---\ntitle: Old\n---\nBody\n";
+        let (_, span) = front_matter_span(content).unwrap();
+
+        let mut edited = content.to_string();
+        edited.replace_range(span, "---\ntitle: New\n---");
+        assert_eq!(edited, "---\ntitle: New\n---\nBody\n");
+    }
+
+    #[test]
+    fn test_front_matter_span_none() {
+        assert!(front_matter_span("Just a body").is_none());
+        assert!(front_matter_span("").is_none());
+        assert!(front_matter_span("---\nunclosed").is_none());
+        assert!(front_matter_span("{\"unclosed\": 1").is_none());
+    }
+
+    #[test]
+    fn test_extract_all_metadata() {
+        let first = "---\ntitle: First\n---\n";
+        let body = "Intro text.\n\n---\n\nA thematic break above.\n";
+        let second = "---\ntitle: Second\nsection: 2\n---\nMore text.";
+        let content = format!("{}{}{}", first, body, second);
+
+        let blocks = extract_all_metadata(&content);
+        assert_eq!(blocks.len(), 2);
+
+        assert_eq!(blocks[0].0, 0);
+        assert_eq!(blocks[0].1.get("title").unwrap(), "First");
+
+        let second_offset = first.len() + body.len();
+        assert_eq!(blocks[1].0, second_offset);
+        assert!(
+            content[second_offset..].starts_with("---\ntitle: Second")
+        );
+        assert_eq!(blocks[1].1.get("title").unwrap(), "Second");
+        assert_eq!(blocks[1].1.get("section").unwrap(), "2");
+    }
+
+    #[test]
+    fn test_extract_metadata_at() {
+        let first = "---\ntitle: First\n---\nIntro text.\n\n";
+        let second = "---\ntitle: Second\nsection: 2\n---\nMore text.";
+        let content = format!("{}{}", first, second);
 
-    // Attempt to parse the date in different formats using DateTime methods
-    let parsed_date = DateTime::parse(&date)
-        .or_else(|_| {
-            DateTime::parse_custom_format(&date, "[year]-[month]-[day]")
-        })
-        .or_else(|_| {
-            DateTime::parse_custom_format(&date, "[month]/[day]/[year]")
-        })
-        .map_err(|e| {
-            MetadataError::DateParseError(format!(
-                "Failed to parse date: {}",
-                e
-            ))
-        })?;
+        let offset = extract_all_metadata(&content)[1].0;
+        assert_eq!(offset, first.len());
 
-    // Format the date to the standardized YYYY-MM-DD format
-    Ok(format!(
-        "{:04}-{:02}-{:02}",
-        parsed_date.year(),
-        parsed_date.month() as u8,
-        parsed_date.day()
-    ))
-}
+        let metadata = extract_metadata_at(&content, offset).unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "Second");
+        assert_eq!(metadata.get("section").unwrap(), "2");
 
-/// Ensures that all required fields are present in the metadata.
-///
-/// # Arguments
-///
-/// * `metadata` - A reference to the `Metadata` instance to check.
-///
-/// # Returns
-///
-/// A `Result<()>` if all required fields are present, or a `MetadataError` if any are missing.
-///
-/// # Errors
-///
-/// Returns a `MetadataError::MissingFieldError` if any required field is missing.
-fn ensure_required_fields(
-    metadata: &Metadata,
-) -> Result<(), MetadataError> {
-    let required_fields = ["title", "date"];
+        let metadata = extract_metadata_at(&content, 0).unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "First");
+    }
 
-    for &field in &required_fields {
-        if !metadata.contains_key(field) {
-            return Err(MetadataError::MissingFieldError(
-                field.to_string(),
+    #[test]
+    fn test_extract_metadata_at_invalid_offset() {
+        let content = "---\ntitle: Café\n---\n";
+        let accent = content.find('é').unwrap();
+
+        for start in [content.len() + 1, accent + 1] {
+            assert!(matches!(
+                extract_metadata_at(content, start),
+                Err(MetadataError::ExtractionError { .. })
             ));
         }
+        assert!(extract_metadata_at(content, content.len()).is_err());
     }
 
-    Ok(())
-}
+    #[test]
+    fn test_extract_all_metadata_none() {
+        assert!(extract_all_metadata("Just text\n---\nno blocks")
+            .is_empty());
+        assert!(extract_all_metadata("").is_empty());
+    }
 
-/// Generates derived fields for the metadata.
-///
-/// Currently, this function generates a URL slug from the title if not already present.
-///
-/// # Arguments
-///
-/// * `metadata` - A mutable reference to the `Metadata` instance to update.
-fn generate_derived_fields(metadata: &mut Metadata) {
-    if !metadata.contains_key("slug") {
-        if let Some(title) = metadata.get("title") {
-            let slug = generate_slug(title);
-            metadata.insert("slug".to_string(), slug);
-        }
+    #[test]
+    fn test_parse_metadata_string_yaml_without_fences() {
+        let input = "title: Raw YAML\nauthor:\n  name: Jane\n";
+
+        let metadata =
+            parse_metadata_string(input, FrontMatterFormat::Yaml)
+                .unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "Raw YAML");
+        assert_eq!(metadata.get("author.name").unwrap(), "Jane");
     }
-}
 
-/// Generates a URL slug from the given title.
-///
-/// # Arguments
-///
-/// * `title` - A string slice containing the title to convert to a slug.
-///
-/// # Returns
-///
-/// A `String` containing the generated slug.
-fn generate_slug(title: &str) -> String {
-    title.to_lowercase().replace(' ', "-")
-}
+    #[test]
+    fn test_parse_metadata_string_json_without_fences() {
+        let input =
+            r#"{"title": "Raw JSON", "description": "No fences"}"#;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use dtt::dtt_parse;
+        let metadata =
+            parse_metadata_string(input, FrontMatterFormat::Json)
+                .unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "Raw JSON");
+        assert_eq!(metadata.get("description").unwrap(), "No fences");
+    }
 
     #[test]
-    fn test_standardize_date() {
-        let test_cases = vec![
-            ("2023-05-20T15:30:00Z", "2023-05-20"),
-            ("2023-05-20", "2023-05-20"),
-            ("20/05/2023", "2023-05-20"), // European format DD/MM/YYYY
-        ];
+    fn test_parse_metadata_string_toml_without_fences() {
+        let input = "title = \"Raw TOML\"\n[extra]\nkey = \"value\"\n";
 
-        for (input, expected) in test_cases {
-            let result = standardize_date(input);
-            assert!(result.is_ok(), "Failed for input: {}", input);
-            assert_eq!(result.unwrap(), expected);
-        }
+        let metadata =
+            parse_metadata_string(input, FrontMatterFormat::Toml)
+                .unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "Raw TOML");
+        assert_eq!(metadata.get("extra.key").unwrap(), "value");
     }
 
     #[test]
-    fn test_standardize_date_errors() {
-        assert!(standardize_date("").is_err());
-        assert!(standardize_date("invalid").is_err());
-        assert!(standardize_date("20/05/23").is_err()); // Invalid DD/MM/YY format
+    fn test_parse_metadata_string_invalid_input() {
+        assert!(matches!(
+            parse_metadata_string("title: [", FrontMatterFormat::Yaml),
+            Err(MetadataError::YamlError(_))
+        ));
+        assert!(matches!(
+            parse_metadata_string("[1, 2]", FrontMatterFormat::Json),
+            Err(MetadataError::ExtractionError { .. })
+        ));
+        assert!(matches!(
+            parse_metadata_string("title = ", FrontMatterFormat::Toml),
+            Err(MetadataError::TomlError(_))
+        ));
     }
 
     #[test]
-    fn test_date_format() {
-        let dt = dtt_parse!("2023-01-01T12:00:00+00:00").unwrap();
-        let formatted = format!(
-            "{:04}-{:02}-{:02}",
-            dt.year(),
-            dt.month() as u8,
-            dt.day()
-        );
-        assert_eq!(formatted, "2023-01-01");
+    fn test_parse_metadata_string_empty_block() {
+        for input in ["", "  \n", "# only a comment\n"] {
+            let metadata =
+                parse_metadata_string(input, FrontMatterFormat::Yaml)
+                    .unwrap();
+            assert!(metadata.as_map().is_empty());
+        }
+        assert!(matches!(
+            parse_metadata_string("", FrontMatterFormat::Json),
+            Err(MetadataError::JsonError(_))
+        ));
     }
 
     #[test]
-    fn test_generate_slug() {
-        assert_eq!(generate_slug("Hello World"), "hello-world");
-        assert_eq!(generate_slug("Test 123"), "test-123");
-        assert_eq!(generate_slug("  Spaces  "), "--spaces--");
+    fn test_parse_front_matter() {
+        let metadata = parse_front_matter(
+            "title: Split\n",
+            FrontMatterFormat::Yaml,
+        )
+        .unwrap();
+        assert_eq!(metadata.get("title").unwrap(), "Split");
+
+        assert!(parse_front_matter("", FrontMatterFormat::Yaml)
+            .unwrap()
+            .as_map()
+            .is_empty());
+        assert!(matches!(
+            parse_front_matter("title = ", FrontMatterFormat::Toml),
+            Err(MetadataError::TomlError(_))
+        ));
     }
 
     #[test]
-    fn test_process_metadata() {
-        let mut metadata = Metadata::new(HashMap::new());
-        metadata.insert("title".to_string(), "Test Title".to_string());
-        metadata.insert(
-            "date".to_string(),
-            "2023-05-20T15:30:00Z".to_string(),
-        );
+    fn test_extract_yaml_normalize_scalars() {
+        let content = "---\nflag: True\nanswer: YES\nhex: 0x10\nratio: 1.50\nshout: TRUE\n---\nBody";
 
-        let processed = process_metadata(&metadata).unwrap();
-        assert_eq!(processed.get("title").unwrap(), "Test Title");
-        assert_eq!(processed.get("date").unwrap(), "2023-05-20");
-        assert_eq!(processed.get("slug").unwrap(), "test-title");
+        let metadata = extract_metadata(content).unwrap();
+        assert_eq!(metadata.get("flag").unwrap(), "true");
+        assert_eq!(metadata.get("answer").unwrap(), "YES");
+        assert_eq!(metadata.get("hex").unwrap(), "16");
+        assert_eq!(metadata.get("ratio").unwrap(), "1.5");
+        assert_eq!(metadata.get("shout").unwrap(), "true");
     }
 
     #[test]
-    fn test_extract_metadata() {
+    fn test_extract_yaml_preserve_scalars() {
+        let content = r#"---
+flag: True
+answer: YES
+hex: 0x10
+ratio: 1.50
+quoted: "0x20"
+empty: ~
+settings:
+  draft: FALSE
+  octal: 0o17
+authors:
+  - name: A
+    age: +42
+flags: [True, no, 0x1, ~, "x, y"]
+anchor: &n 0x5
+alias: *n
+---
+Body"#;
+        let options = ExtractOptions {
+            normalize_scalars: false,
+            ..ExtractOptions::default()
+        };
+
+        let metadata =
+            extract_metadata_with_options(content, &options).unwrap();
+        assert_eq!(metadata.get("flag").unwrap(), "True");
+        assert_eq!(metadata.get("answer").unwrap(), "YES");
+        assert_eq!(metadata.get("hex").unwrap(), "0x10");
+        assert_eq!(metadata.get("ratio").unwrap(), "1.50");
+        assert_eq!(metadata.get("quoted").unwrap(), "0x20");
+        assert_eq!(metadata.get("empty").unwrap(), "");
+        assert_eq!(metadata.get("settings.draft").unwrap(), "FALSE");
+        assert_eq!(metadata.get("settings.octal").unwrap(), "0o17");
+        assert_eq!(metadata.get("authors.0.age").unwrap(), "+42");
+        assert_eq!(
+            metadata.get("flags").unwrap(),
+            r#"[True, no, 0x1, "x, y"]"#
+        );
+        assert_eq!(metadata.get("anchor").unwrap(), "0x5");
+        // Aliases are resolved by the YAML parser and stay normalized.
+        assert_eq!(metadata.get("alias").unwrap(), "5");
+    }
+
+    #[test]
+    fn test_extract_yaml_non_string_scalars() {
         let yaml_content = r#"---
-title: YAML Test
+title: Scalars
 date: 2023-05-20
+timestamp: !!timestamp 2023-05-21
+published: true
+year: 2023
+rating: 4.5
+draft: ~
+ids: [1, 2, three]
 ---
 Content here"#;
 
-        let toml_content = r#"+++
-title = "TOML Test"
-date = "2023-05-20"
-+++
-Content here"#;
-
-        let json_content = r#"{
-"title": "JSON Test",
-"date": "2023-05-20"
-}
-Content here"#;
+        let metadata = extract_metadata(yaml_content).unwrap();
+        assert_eq!(metadata.get("date").unwrap(), "2023-05-20");
+        assert_eq!(metadata.get("timestamp").unwrap(), "2023-05-21");
+        assert_eq!(metadata.get("published").unwrap(), "true");
+        assert_eq!(metadata.get("year").unwrap(), "2023");
+        assert_eq!(metadata.get("rating").unwrap(), "4.5");
+        assert_eq!(metadata.get("draft").unwrap(), "");
+        assert_eq!(metadata.get("ids").unwrap(), "[1, 2, three]");
 
-        let yaml_metadata = extract_metadata(yaml_content).unwrap();
-        assert_eq!(yaml_metadata.get("title").unwrap(), "YAML Test");
+        let processed = process_metadata(&metadata).unwrap();
+        assert_eq!(processed.get("date").unwrap(), "2023-05-20");
+    }
 
-        let toml_metadata = extract_metadata(toml_content).unwrap();
-        assert_eq!(toml_metadata.get("title").unwrap(), "TOML Test");
+    #[test]
+    fn test_list_element_with_comma_is_not_split() {
+        let yaml_content = "---\ntags: [\"a, b\", c]\n---\nContent";
+        let toml_content =
+            "+++\ntags = [\"a, b\", \"c\"]\n+++\nContent";
 
-        let json_metadata = extract_metadata(json_content).unwrap();
-        assert_eq!(json_metadata.get("title").unwrap(), "JSON Test");
+        for content in [yaml_content, toml_content] {
+            let metadata = extract_metadata(content).unwrap();
+            assert_eq!(metadata.get("tags").unwrap(), "[\"a, b\", c]");
+            assert_eq!(
+                metadata.get_list("tags").unwrap(),
+                vec!["a, b".to_string(), "c".to_string()]
+            );
+        }
     }
 
     #[test]
-    fn test_extract_metadata_failure() {
-        let invalid_content = "This content has no metadata";
-        assert!(extract_metadata(invalid_content).is_err());
+    fn test_get_bool() {
+        let mut metadata = Metadata::new(HashMap::new());
+        for (value, expected) in [
+            ("true", Some(true)),
+            (" YES ", Some(true)),
+            ("On", Some(true)),
+            ("1", Some(true)),
+            ("False", Some(false)),
+            ("no", Some(false)),
+            ("off", Some(false)),
+            ("0", Some(false)),
+            ("maybe", None),
+            ("", None),
+        ] {
+            metadata.insert("flag".to_string(), value.to_string());
+            assert_eq!(
+                metadata.get_bool("flag"),
+                expected,
+                "{:?}",
+                value
+            );
+        }
+        assert_eq!(metadata.get_bool("missing"), None);
     }
 
     #[test]
-    fn test_ensure_required_fields() {
-        let mut metadata = Metadata::new(HashMap::new());
-        metadata.insert("title".to_string(), "Test".to_string());
-        metadata.insert("date".to_string(), "2023-05-20".to_string());
+    fn test_is_draft() {
+        let draft =
+            extract_metadata("---\ntitle: A\ndraft: true\n---\n")
+                .unwrap();
+        assert!(is_draft(&draft));
 
-        assert!(ensure_required_fields(&metadata).is_ok());
+        let published =
+            extract_metadata("---\ntitle: A\ndraft: false\n---\n")
+                .unwrap();
+        assert!(!is_draft(&published));
 
-        let mut incomplete_metadata = Metadata::new(HashMap::new());
-        incomplete_metadata
-            .insert("title".to_string(), "Test".to_string());
+        let absent = extract_metadata("---\ntitle: A\n---\n").unwrap();
+        assert!(!is_draft(&absent));
 
-        assert!(ensure_required_fields(&incomplete_metadata).is_err());
+        let toml =
+            extract_metadata("+++\ndraft = true\n+++\n").unwrap();
+        assert!(is_draft(&toml));
     }
 
     #[test]
-    fn test_generate_derived_fields() {
-        let mut metadata = Metadata::new(HashMap::new());
-        metadata.insert("title".to_string(), "Test Title".to_string());
+    fn test_get_path() {
+        let content = "---\ntitle: Post\nauthor:\n  name: Jane\n  email: jane@example.com\n  social:\n    x: \"@jane\"\n---\n";
+        let metadata = extract_metadata(content).unwrap();
 
-        generate_derived_fields(&mut metadata);
-
-        assert_eq!(metadata.get("slug").unwrap(), "test-title");
+        assert_eq!(
+            metadata.get_path(&["author", "email"]).unwrap(),
+            "jane@example.com"
+        );
+        assert_eq!(
+            metadata.get_path(&["author", "social", "x"]).unwrap(),
+            "@jane"
+        );
+        assert_eq!(metadata.get_path(&["title"]).unwrap(), "Post");
+        assert!(metadata.get_path(&["author"]).is_none());
+        assert!(metadata.get_path(&["author", "phone"]).is_none());
+        assert!(metadata.get_path(&[]).is_none());
     }
 
     #[test]
-    fn test_metadata_methods() {
-        let mut metadata = Metadata::new(HashMap::new());
-        metadata.insert("key".to_string(), "value".to_string());
+    fn test_get_subtree() {
+        let content = "---\nauthor:\n  name: Jane\n  social:\n    x: \"@jane\"\nauthors: [A, B]\nauthorship: solo\n---\n";
+        let metadata = extract_metadata(content).unwrap();
 
-        assert_eq!(metadata.get("key"), Some(&"value".to_string()));
-        assert!(metadata.contains_key("key"));
-        assert!(!metadata.contains_key("nonexistent"));
+        let author = metadata.get_subtree("author");
+        assert_eq!(author.len(), 2);
+        assert_eq!(author["name"], "Jane");
+        assert_eq!(author["social.x"], "@jane");
+        assert_eq!(metadata.get_subtree("author."), author);
 
-        let old_value =
-            metadata.insert("key".to_string(), "new_value".to_string());
-        assert_eq!(old_value, Some("value".to_string()));
-        assert_eq!(metadata.get("key"), Some(&"new_value".to_string()));
+        let social = metadata.get_subtree("author.social");
+        assert_eq!(social.len(), 1);
+        assert_eq!(social["x"], "@jane");
 
-        let inner = metadata.into_inner();
-        assert_eq!(inner.get("key"), Some(&"new_value".to_string()));
+        assert!(metadata.get_subtree("authors").is_empty());
+        assert!(metadata.get_subtree("missing").is_empty());
     }
 
     #[test]
-    fn test_process_metadata_with_invalid_date() {
+    fn test_get_list() {
         let mut metadata = Metadata::new(HashMap::new());
-        metadata.insert("title".to_string(), "Test Title".to_string());
-        metadata.insert("date".to_string(), "invalid_date".to_string());
+        metadata.insert(
+            "tags".to_string(),
+            "[rust, metadata, testing]".to_string(),
+        );
+        metadata.insert(
+            "quotes".to_string(),
+            format_inline_list(
+                ["say \"hi\"", "back\\slash"].into_iter(),
+            ),
+        );
+        metadata.insert("empty".to_string(), "[]".to_string());
+        metadata.insert("title".to_string(), "Plain".to_string());
 
-        assert!(process_metadata(&metadata).is_err());
+        assert_eq!(
+            metadata.get_list("tags").unwrap(),
+            vec!["rust", "metadata", "testing"]
+        );
+        assert_eq!(
+            metadata.get_list("quotes").unwrap(),
+            vec!["say \"hi\"", "back\\slash"]
+        );
+        assert!(metadata.get_list("empty").unwrap().is_empty());
+        assert_eq!(metadata.get_list("title").unwrap(), vec!["Plain"]);
+        assert!(metadata.get_list("missing").is_none());
     }
 
     #[test]
-    fn test_extract_yaml_metadata_with_complex_structure() {
+    fn test_extract_yaml_sequence_of_mappings() {
         let yaml_content = r#"---
-title: Complex YAML Test
-date: 2023-05-20
-author:
-  name: John Doe
-  email: john@example.com
-tags:
-  - rust
-  - metadata
-  - testing
+title: Alternates
+alternates:
+  - lang: en
+    url: https://example.com/en
+  - lang: fr
+    url: https://example.com/fr
 ---
 Content here"#;
 
         let metadata = extract_metadata(yaml_content).unwrap();
-        assert_eq!(metadata.get("title").unwrap(), "Complex YAML Test");
-        assert_eq!(metadata.get("date").unwrap(), "2023-05-20");
-        assert_eq!(metadata.get("author.name").unwrap(), "John Doe");
+        assert_eq!(metadata.get("alternates.0.lang").unwrap(), "en");
         assert_eq!(
-            metadata.get("author.email").unwrap(),
-            "john@example.com"
+            metadata.get("alternates.1.url").unwrap(),
+            "https://example.com/fr"
         );
+        assert!(!metadata.contains_key("alternates"));
+    }
+
+    #[test]
+    fn test_extract_yaml_anchors_and_aliases() {
+        let yaml_content = r#"---
+defaults: &defaults
+  author: Jane Doe
+  lang: en
+post:
+  meta: *defaults
+name: &name Example
+site: *name
+translation:
+  <<: *defaults
+  lang: fr
+---
+Content here"#;
+
+        let metadata = extract_metadata(yaml_content).unwrap();
         assert_eq!(
-            metadata.get("tags").unwrap(),
-            "[rust, metadata, testing]"
+            metadata.get("post.meta.author").unwrap(),
+            "Jane Doe"
+        );
+        assert_eq!(metadata.get("post.meta.lang").unwrap(), "en");
+        assert_eq!(metadata.get("site").unwrap(), "Example");
+        assert_eq!(
+            metadata.get("translation.author").unwrap(),
+            "Jane Doe"
         );
+        assert_eq!(metadata.get("translation.lang").unwrap(), "fr");
+        assert!(!metadata.contains_key("translation.<<.author"));
     }
 
     #[test]
-    fn test_extract_toml_metadata_with_complex_structure() {
+    fn test_extract_toml_array_of_tables() {
         let toml_content = r#"+++
-title = "Complex TOML Test"
-date = 2023-05-20
+title = "Alternates"
 
-[author]
-name = "John Doe"
-email = "john@example.com"
+[[alternates]]
+lang = "en"
+url = "https://example.com/en"
 
-tags = ["rust", "metadata", "testing"]
+[[alternates]]
+lang = "fr"
+url = "https://example.com/fr"
 +++
 Content here"#;
 
         let metadata = extract_metadata(toml_content).unwrap();
+        assert_eq!(metadata.get("alternates.0.lang").unwrap(), "en");
         assert_eq!(
-            metadata.get("title").expect("Missing 'title' key"),
-            "Complex TOML Test"
-        );
-        assert_eq!(
-            metadata.get("date").expect("Missing 'date' key"),
-            "2023-05-20"
-        );
-        assert_eq!(
-            metadata
-                .get("author.name")
-                .expect("Missing 'author.name' key"),
-            "John Doe"
-        );
-        assert_eq!(
-            metadata
-                .get("author.email")
-                .expect("Missing 'author.email' key"),
-            "john@example.com"
-        );
-        assert_eq!(
-            metadata
-                .get("author.tags")
-                .expect("Missing 'author.tags' key"),
-            "[rust, metadata, testing]"
+            metadata.get("alternates.1.url").unwrap(),
+            "https://example.com/fr"
         );
+        assert!(!metadata.contains_key("alternates"));
     }
 
     #[test]
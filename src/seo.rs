@@ -0,0 +1,493 @@
+//! SEO analysis and linting module.
+//!
+//! This module provides functionality for checking metadata against common
+//! search engine and social media conventions, reporting non-fatal warnings.
+
+use crate::metadata::Metadata;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    path::PathBuf,
+};
+
+/// Represents a non-fatal SEO issue found in the metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeoWarning {
+    /// The metadata field the warning relates to.
+    pub field: String,
+    /// A descriptive message about the issue.
+    pub message: String,
+}
+
+impl SeoWarning {
+    /// Creates a new `SeoWarning` for the given field.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The metadata field the warning relates to.
+    /// * `message` - A descriptive message about the issue.
+    ///
+    /// # Returns
+    ///
+    /// A new `SeoWarning` instance.
+    pub fn new(
+        field: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        SeoWarning {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Implement `Display` for `SeoWarning`.
+impl fmt::Display for SeoWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Checks the metadata for SEO inconsistencies.
+///
+/// The following checks are performed:
+/// - An `og:type` of `article` requires an `article:published_time`,
+///   either set directly or derivable from the `date` field.
+///
+/// # Arguments
+///
+/// * `metadata` - A reference to a HashMap containing the metadata.
+///
+/// # Returns
+///
+/// A vector of `SeoWarning`s, empty if no issues were found.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::seo::lint_metadata;
+/// use std::collections::HashMap;
+///
+/// let mut metadata = HashMap::new();
+/// metadata.insert("og:type".to_string(), "article".to_string());
+///
+/// let warnings = lint_metadata(&metadata);
+/// assert_eq!(warnings[0].field, "og:type");
+/// ```
+pub fn lint_metadata(
+    metadata: &HashMap<String, String>,
+) -> Vec<SeoWarning> {
+    let mut warnings = Vec::new();
+
+    let is_article = matches!(
+        metadata.get("og:type"),
+        Some(og_type) if og_type.trim() == "article"
+    );
+    if is_article
+        && !metadata.contains_key("article:published_time")
+        && !metadata.contains_key("date")
+    {
+        warnings.push(SeoWarning::new(
+            "og:type",
+            "og:type is `article` but no article:published_time or date is available",
+        ));
+    }
+
+    warnings
+}
+
+/// The maximum lengths, in characters, that social platforms display for
+/// Open Graph and Twitter titles and descriptions, as used by
+/// `validate_social_limits`.
+pub const SOCIAL_LIMITS: [(&str, usize); 4] = [
+    ("og:title", 95),
+    ("og:description", 200),
+    ("twitter:title", 70),
+    ("twitter:description", 200),
+];
+
+/// Checks Open Graph and Twitter titles and descriptions against the
+/// lengths social platforms display.
+///
+/// Each field listed in `SOCIAL_LIMITS` that is longer than its limit
+/// yields a warning. Lengths are counted in characters after trimming
+/// surrounding whitespace, and absent fields are not checked.
+///
+/// # Arguments
+///
+/// * `metadata` - A reference to a HashMap containing the metadata.
+///
+/// # Returns
+///
+/// A vector of `SeoWarning`s in the order of `SOCIAL_LIMITS`, empty if
+/// every field fits.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::seo::validate_social_limits;
+/// use std::collections::HashMap;
+///
+/// let mut metadata = HashMap::new();
+/// metadata.insert("twitter:title".to_string(), "x".repeat(71));
+///
+/// let warnings = validate_social_limits(&metadata);
+/// assert_eq!(warnings[0].field, "twitter:title");
+/// ```
+pub fn validate_social_limits(
+    metadata: &HashMap<String, String>,
+) -> Vec<SeoWarning> {
+    SOCIAL_LIMITS
+        .iter()
+        .filter_map(|&(field, limit)| {
+            let length = metadata.get(field)?.trim().chars().count();
+            if length <= limit {
+                return None;
+            }
+            Some(SeoWarning::new(
+                field,
+                format!(
+                    "{} is {} characters long and may be truncated after {}",
+                    field, length, limit
+                ),
+            ))
+        })
+        .collect()
+}
+
+/// Finds canonical URLs claimed by more than one page.
+///
+/// The canonical URL of a page is read from the `canonical` key, falling
+/// back to `og:url`, as in `MetaTagGroups::generate_canonical_link`.
+/// Pages without either key are ignored.
+///
+/// # Arguments
+///
+/// * `entries` - The `(path, metadata)` pairs of the pages in a batch.
+///
+/// # Returns
+///
+/// A vector of `(canonical, paths)` pairs, one per URL shared by two or
+/// more pages, sorted by URL. The paths keep the order of `entries`.
+pub fn detect_canonical_conflicts(
+    entries: &[(PathBuf, Metadata)],
+) -> Vec<(String, Vec<PathBuf>)> {
+    let mut by_canonical: BTreeMap<&str, Vec<PathBuf>> =
+        BTreeMap::new();
+
+    for (path, metadata) in entries {
+        let canonical = metadata
+            .get("canonical")
+            .or_else(|| metadata.get("og:url"))
+            .map(|url| url.trim())
+            .filter(|url| !url.is_empty());
+        if let Some(canonical) = canonical {
+            by_canonical
+                .entry(canonical)
+                .or_default()
+                .push(path.clone());
+        }
+    }
+
+    by_canonical
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .map(|(canonical, paths)| (canonical.to_string(), paths))
+        .collect()
+}
+
+/// Computes the occurrence count and density of keywords in a body.
+///
+/// Matching is case-insensitive and on whole words, so `rust` does not
+/// match `trust`. Keywords made of several words are matched as phrases.
+/// The density is the number of occurrences divided by the total number
+/// of words in the body, or `0.0` for an empty body.
+///
+/// # Arguments
+///
+/// * `keywords` - The keywords to look for.
+/// * `body` - The body text to search.
+///
+/// # Returns
+///
+/// A vector of `(keyword, count, density)` tuples in the order of
+/// `keywords`.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::seo::keyword_density;
+///
+/// let density = keyword_density(&["rust".to_string()], "Rust is fast. I like rust.");
+/// assert_eq!(density[0].1, 2);
+/// assert!((density[0].2 - 2.0 / 6.0).abs() < f64::EPSILON);
+/// ```
+pub fn keyword_density(
+    keywords: &[String],
+    body: &str,
+) -> Vec<(String, usize, f64)> {
+    let words = tokenize(body);
+
+    keywords
+        .iter()
+        .map(|keyword| {
+            let phrase = tokenize(keyword);
+            let count = if phrase.is_empty() {
+                0
+            } else {
+                words
+                    .windows(phrase.len())
+                    .filter(|window| *window == phrase.as_slice())
+                    .count()
+            };
+            let density = if words.is_empty() {
+                0.0
+            } else {
+                count as f64 / words.len() as f64
+            };
+            (keyword.clone(), count, density)
+        })
+        .collect()
+}
+
+/// Computes the density of each keyword in a body, keyed by keyword.
+///
+/// This is a map-returning variant of `keyword_density`, with the same
+/// case-insensitive whole-word matching. Each density is the fraction of
+/// the words in the body that are occurrences of the keyword, and is
+/// `0.0` for every keyword when the body has no words.
+///
+/// # Arguments
+///
+/// * `body` - The body text to search.
+/// * `keywords` - The keywords to look for.
+///
+/// # Returns
+///
+/// A `HashMap` from each keyword, as given, to its density.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::seo::keyword_density_map;
+///
+/// let density = keyword_density_map("Rust is fast. I like rust.", &["rust".to_string()]);
+/// assert!((density["rust"] - 2.0 / 6.0).abs() < f64::EPSILON);
+/// ```
+pub fn keyword_density_map(
+    body: &str,
+    keywords: &[String],
+) -> HashMap<String, f64> {
+    keyword_density(keywords, body)
+        .into_iter()
+        .map(|(keyword, _, density)| (keyword, density))
+        .collect()
+}
+
+/// Splits text into lowercase words.
+pub(crate) fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|word| word.trim_matches('\''))
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_article_without_date() {
+        let mut metadata = HashMap::new();
+        metadata.insert("og:type".to_string(), "article".to_string());
+        metadata.insert("title".to_string(), "Post".to_string());
+
+        let warnings = lint_metadata(&metadata);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].field, "og:type");
+        assert!(warnings[0].to_string().starts_with("og:type: "));
+    }
+
+    #[test]
+    fn test_lint_article_with_date() {
+        let mut metadata = HashMap::new();
+        metadata.insert("og:type".to_string(), "article".to_string());
+        metadata.insert("date".to_string(), "2023-05-20".to_string());
+        assert!(lint_metadata(&metadata).is_empty());
+
+        let mut metadata = HashMap::new();
+        metadata.insert("og:type".to_string(), "article".to_string());
+        metadata.insert(
+            "article:published_time".to_string(),
+            "2023-05-20T00:00:00Z".to_string(),
+        );
+        assert!(lint_metadata(&metadata).is_empty());
+    }
+
+    #[test]
+    fn test_validate_social_limits_boundaries() {
+        for &(field, limit) in &SOCIAL_LIMITS {
+            let mut metadata = HashMap::new();
+            metadata.insert(field.to_string(), "é".repeat(limit));
+            assert!(
+                validate_social_limits(&metadata).is_empty(),
+                "{} at {} characters should pass",
+                field,
+                limit
+            );
+
+            metadata.insert(field.to_string(), "é".repeat(limit + 1));
+            let warnings = validate_social_limits(&metadata);
+            assert_eq!(warnings.len(), 1);
+            assert_eq!(warnings[0].field, field);
+            assert!(warnings[0]
+                .message
+                .contains(&format!("{} characters", limit + 1)));
+        }
+    }
+
+    #[test]
+    fn test_validate_social_limits_trims_and_ignores_missing() {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "twitter:title".to_string(),
+            format!("  {}  ", "x".repeat(70)),
+        );
+        metadata.insert("title".to_string(), "x".repeat(500));
+        assert!(validate_social_limits(&metadata).is_empty());
+
+        metadata.insert("og:description".to_string(), "x".repeat(201));
+        metadata
+            .insert("twitter:description".to_string(), "x".repeat(201));
+        let fields = validate_social_limits(&metadata)
+            .into_iter()
+            .map(|warning| warning.field)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            vec!["og:description", "twitter:description"]
+        );
+    }
+
+    #[test]
+    fn test_keyword_density() {
+        let body =
+            "Rust makes metadata easy. Metadata in Rust is typed, \
+                    and trusted metadata generation is fast.";
+        let keywords = vec![
+            "rust".to_string(),
+            "Metadata".to_string(),
+            "metadata generation".to_string(),
+            "python".to_string(),
+        ];
+
+        let density = keyword_density(&keywords, body);
+        let total = 15.0;
+        assert_eq!(density.len(), 4);
+        assert_eq!(density[0], ("rust".to_string(), 2, 2.0 / total));
+        assert_eq!(
+            density[1],
+            ("Metadata".to_string(), 3, 3.0 / total)
+        );
+        assert_eq!(
+            density[2],
+            ("metadata generation".to_string(), 1, 1.0 / total)
+        );
+        assert_eq!(density[3], ("python".to_string(), 0, 0.0));
+    }
+
+    #[test]
+    fn test_keyword_density_map() {
+        let body =
+            "SEO tools: seo audits, SEO reports and keyword tools.";
+        let keywords = vec![
+            "seo".to_string(),
+            "Tools".to_string(),
+            "keyword tools".to_string(),
+            "rank".to_string(),
+        ];
+
+        let density = keyword_density_map(body, &keywords);
+        let total = 9.0;
+        assert_eq!(density.len(), 4);
+        assert_eq!(density["seo"], 3.0 / total);
+        assert_eq!(density["Tools"], 2.0 / total);
+        assert_eq!(density["keyword tools"], 1.0 / total);
+        assert_eq!(density["rank"], 0.0);
+    }
+
+    #[test]
+    fn test_keyword_density_map_zero_words() {
+        for body in ["", "  ...  !"] {
+            let density =
+                keyword_density_map(body, &["seo".to_string()]);
+            assert_eq!(density["seo"], 0.0);
+        }
+    }
+
+    #[test]
+    fn test_keyword_density_empty_body() {
+        let density = keyword_density(&["rust".to_string()], "");
+        assert_eq!(density, vec![("rust".to_string(), 0, 0.0)]);
+    }
+
+    fn page(key: &str, url: &str) -> Metadata {
+        let mut metadata = Metadata::new(HashMap::new());
+        metadata.insert(key.to_string(), url.to_string());
+        metadata
+    }
+
+    #[test]
+    fn test_detect_canonical_conflicts() {
+        let entries = vec![
+            (
+                PathBuf::from("content/a.md"),
+                page("canonical", "https://example.com/post"),
+            ),
+            (
+                PathBuf::from("content/b.md"),
+                page("canonical", "https://example.com/other"),
+            ),
+            (
+                PathBuf::from("content/c.md"),
+                page("og:url", "https://example.com/post"),
+            ),
+            (PathBuf::from("content/d.md"), page("title", "No URL")),
+        ];
+
+        let conflicts = detect_canonical_conflicts(&entries);
+        assert_eq!(
+            conflicts,
+            vec![(
+                "https://example.com/post".to_string(),
+                vec![
+                    PathBuf::from("content/a.md"),
+                    PathBuf::from("content/c.md"),
+                ],
+            )]
+        );
+    }
+
+    #[test]
+    fn test_detect_canonical_conflicts_none() {
+        let entries = vec![
+            (
+                PathBuf::from("a.md"),
+                page("canonical", "https://a.com/"),
+            ),
+            (
+                PathBuf::from("b.md"),
+                page("canonical", "https://b.com/"),
+            ),
+        ];
+        assert!(detect_canonical_conflicts(&entries).is_empty());
+    }
+
+    #[test]
+    fn test_lint_website_without_date() {
+        let mut metadata = HashMap::new();
+        metadata.insert("og:type".to_string(), "website".to_string());
+        assert!(lint_metadata(&metadata).is_empty());
+    }
+}
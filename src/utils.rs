@@ -44,12 +44,215 @@
 /// potentially dangerous characters. However, it should not be relied upon as the sole
 /// method of sanitizing user input for use in HTML contexts.
 pub fn escape_html(value: &str) -> String {
-    value
-        .replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&#x27;")
+    escape_html_with(value, EscapeFlags::ALL)
+}
+
+/// A set of characters for `escape_html_with` to escape.
+///
+/// Flags are combined with `|`, as in
+/// `EscapeFlags::AMPERSAND | EscapeFlags::LESS_THAN`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EscapeFlags(u8);
+
+impl EscapeFlags {
+    /// Escape `&` as `&amp;`.
+    pub const AMPERSAND: Self = EscapeFlags(1);
+    /// Escape `<` as `&lt;`.
+    pub const LESS_THAN: Self = EscapeFlags(1 << 1);
+    /// Escape `>` as `&gt;`.
+    pub const GREATER_THAN: Self = EscapeFlags(1 << 2);
+    /// Escape `"` as `&quot;`.
+    pub const DOUBLE_QUOTE: Self = EscapeFlags(1 << 3);
+    /// Escape `'` as `&#x27;`.
+    pub const SINGLE_QUOTE: Self = EscapeFlags(1 << 4);
+    /// Escape all five characters, as `escape_html` does.
+    pub const ALL: Self = EscapeFlags(0b1_1111);
+    /// Escape `&`, `<`, and `>`, which is enough for text nodes.
+    pub const TEXT: Self = EscapeFlags(0b0_0111);
+    /// Escape `&`, `<`, `>`, and `"`, for double-quoted attribute values.
+    pub const DOUBLE_QUOTED_ATTRIBUTE: Self = EscapeFlags(0b0_1111);
+    /// Escape `&`, `<`, `>`, and `'`, for single-quoted attribute values.
+    pub const SINGLE_QUOTED_ATTRIBUTE: Self = EscapeFlags(0b1_0111);
+
+    /// Returns an empty set, which escapes nothing.
+    pub const fn empty() -> Self {
+        EscapeFlags(0)
+    }
+
+    /// Returns `true` if all flags in `other` are set in `self`.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for EscapeFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        EscapeFlags(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for EscapeFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Escapes the chosen subset of special HTML characters in a string.
+///
+/// Each character is replaced with the same entity as in `escape_html`,
+/// but only if its flag is set in `flags`. This allows minimal escaping
+/// for a given HTML context, for example `EscapeFlags::TEXT` for text
+/// nodes or `EscapeFlags::SINGLE_QUOTED_ATTRIBUTE` for `'`-quoted
+/// attribute values.
+///
+/// # Arguments
+///
+/// * `value` - The string to escape.
+/// * `flags` - The characters to escape.
+///
+/// # Returns
+///
+/// A new string with the chosen characters escaped.
+///
+/// # Examples
+///
+/// ```
+/// use metadata_gen::utils::{escape_html_with, EscapeFlags};
+///
+/// assert_eq!(
+///     escape_html_with(r#"<b>"Tom's"</b>"#, EscapeFlags::TEXT),
+///     r#"&lt;b&gt;"Tom's"&lt;/b&gt;"#
+/// );
+/// ```
+pub fn escape_html_with(value: &str, flags: EscapeFlags) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' if flags.contains(EscapeFlags::AMPERSAND) => {
+                escaped.push_str("&amp;")
+            }
+            '<' if flags.contains(EscapeFlags::LESS_THAN) => {
+                escaped.push_str("&lt;")
+            }
+            '>' if flags.contains(EscapeFlags::GREATER_THAN) => {
+                escaped.push_str("&gt;")
+            }
+            '"' if flags.contains(EscapeFlags::DOUBLE_QUOTE) => {
+                escaped.push_str("&quot;")
+            }
+            '\'' if flags.contains(EscapeFlags::SINGLE_QUOTE) => {
+                escaped.push_str("&#x27;")
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escapes special HTML characters, leaving existing entities intact.
+///
+/// This behaves like `escape_html`, except that a `&` which starts a
+/// character reference is not escaped again. Named references such as
+/// `&amp;`, decimal references such as `&#39;`, and hexadecimal references
+/// such as `&#x27;` are recognized, so that already escaped input is not
+/// double escaped.
+///
+/// # Arguments
+///
+/// * `value` - The string to escape.
+///
+/// # Returns
+///
+/// A new string with special HTML characters escaped.
+///
+/// # Examples
+///
+/// ```
+/// use metadata_gen::utils::escape_html_preserve_entities;
+///
+/// assert_eq!(
+///     escape_html_preserve_entities("Tom &amp; Jerry & <friends>"),
+///     "Tom &amp; Jerry &amp; &lt;friends&gt;"
+/// );
+/// ```
+pub fn escape_html_preserve_entities(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for (index, c) in value.char_indices() {
+        match c {
+            '&' if starts_with_entity(&value[index..]) => {
+                escaped.push('&')
+            }
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#x27;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Returns `true` if `value` starts with a character reference such as
+/// `&amp;`, `&#39;`, or `&#x27;`.
+fn starts_with_entity(value: &str) -> bool {
+    let rest = match value.strip_prefix('&') {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let end = match rest.find(';') {
+        Some(end) => end,
+        None => return false,
+    };
+    let name = &rest[..end];
+
+    if let Some(hex) =
+        name.strip_prefix("#x").or_else(|| name.strip_prefix("#X"))
+    {
+        !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit())
+    } else if let Some(decimal) = name.strip_prefix('#') {
+        !decimal.is_empty()
+            && decimal.chars().all(|c| c.is_ascii_digit())
+    } else {
+        name.len() <= 32
+            && name.starts_with(|c: char| c.is_ascii_alphabetic())
+            && name.chars().all(|c| c.is_ascii_alphanumeric())
+    }
+}
+
+/// Escapes special HTML characters in a string for use in an attribute value.
+///
+/// In addition to the characters escaped by `escape_html`, this function
+/// follows the OWASP attribute encoding guidance and replaces:
+/// - `/` with `&#x2F;`
+/// - `` ` `` with `&#x60;`
+///
+/// Use `escape_html` for element content.
+///
+/// # Arguments
+///
+/// * `value` - The string to escape.
+///
+/// # Returns
+///
+/// A new string with special HTML characters escaped.
+///
+/// # Examples
+///
+/// ```
+/// use metadata_gen::utils::escape_html_attr;
+///
+/// assert_eq!(
+///     escape_html_attr("</a> `x`"),
+///     "&lt;&#x2F;a&gt; &#x60;x&#x60;"
+/// );
+/// ```
+pub fn escape_html_attr(value: &str) -> String {
+    escape_html(value)
+        .replace('/', "&#x2F;")
+        .replace('`', "&#x60;")
 }
 
 /// Unescapes HTML entities in a string.
@@ -61,6 +264,7 @@ pub fn escape_html(value: &str) -> String {
 /// - `&quot;` becomes `"`
 /// - `&#x27;` and `&#39;` become `'`
 /// - `&#x2F;` and `&#x2f;` become `/`
+/// - `&#x60;` becomes `` ` ``
 ///
 /// # Arguments
 ///
@@ -96,6 +300,7 @@ pub fn unescape_html(value: &str) -> String {
         .replace("&#39;", "'")
         .replace("&#x2F;", "/")
         .replace("&#x2f;", "/")
+        .replace("&#x60;", "`")
 }
 
 /// Asynchronously reads a file and extracts metadata from its content.
@@ -160,19 +365,134 @@ pub async fn async_extract_metadata_from_file(
         return Ok((
             HashMap::new(),
             Vec::new(),
-            MetaTagGroups {
-                primary: String::new(),
-                apple: String::new(),
-                ms: String::new(),
-                og: String::new(),
-                twitter: String::new(),
-            },
+            MetaTagGroups::default(),
         ));
     }
 
     extract_and_prepare_metadata(&content)
 }
 
+/// The minimum number of words `detect_language` needs to make a guess.
+#[cfg(feature = "whatlang")]
+const MIN_LANGUAGE_WORDS: usize = 5;
+
+/// Detects the language of the given text.
+///
+/// Detection is best-effort, using the trigram models of `whatlang`, and
+/// is only available with the `whatlang` feature. The result is a BCP-47
+/// primary language subtag, such as `en` or `fr`, suitable for the
+/// `<html lang>` attribute.
+///
+/// # Arguments
+///
+/// * `content` - A string slice containing the text to analyze.
+///
+/// # Returns
+///
+/// The detected language code, or `None` if the text has fewer than five
+/// words or the language cannot be detected reliably.
+///
+/// # Example
+///
+/// ```
+/// use metadata_gen::utils::detect_language;
+///
+/// let text = "Le renard brun rapide saute par-dessus le chien paresseux \
+///     et court dans la forêt avant la tombée de la nuit.";
+/// assert_eq!(detect_language(text).as_deref(), Some("fr"));
+/// assert_eq!(detect_language("Hello"), None);
+/// ```
+#[cfg(feature = "whatlang")]
+pub fn detect_language(content: &str) -> Option<String> {
+    if content.split_whitespace().count() < MIN_LANGUAGE_WORDS {
+        return None;
+    }
+
+    let info = whatlang::detect(content)?;
+    if !info.is_reliable() {
+        return None;
+    }
+    Some(language_subtag(info.lang()).to_string())
+}
+
+/// Maps a detected language to its BCP-47 primary language subtag.
+#[cfg(feature = "whatlang")]
+fn language_subtag(lang: whatlang::Lang) -> &'static str {
+    use whatlang::Lang;
+
+    match lang {
+        Lang::Afr => "af",
+        Lang::Aka => "ak",
+        Lang::Amh => "am",
+        Lang::Ara => "ar",
+        Lang::Aze => "az",
+        Lang::Bel => "be",
+        Lang::Ben => "bn",
+        Lang::Bul => "bg",
+        Lang::Cat => "ca",
+        Lang::Ces => "cs",
+        Lang::Cmn => "zh",
+        Lang::Dan => "da",
+        Lang::Deu => "de",
+        Lang::Ell => "el",
+        Lang::Eng => "en",
+        Lang::Epo => "eo",
+        Lang::Est => "et",
+        Lang::Fin => "fi",
+        Lang::Fra => "fr",
+        Lang::Guj => "gu",
+        Lang::Heb => "he",
+        Lang::Hin => "hi",
+        Lang::Hrv => "hr",
+        Lang::Hun => "hu",
+        Lang::Hye => "hy",
+        Lang::Ind => "id",
+        Lang::Ita => "it",
+        Lang::Jav => "jv",
+        Lang::Jpn => "ja",
+        Lang::Kan => "kn",
+        Lang::Kat => "ka",
+        Lang::Khm => "km",
+        Lang::Kor => "ko",
+        Lang::Lat => "la",
+        Lang::Lav => "lv",
+        Lang::Lit => "lt",
+        Lang::Mal => "ml",
+        Lang::Mar => "mr",
+        Lang::Mkd => "mk",
+        Lang::Mya => "my",
+        Lang::Nep => "ne",
+        Lang::Nld => "nl",
+        Lang::Nob => "nb",
+        Lang::Ori => "or",
+        Lang::Pan => "pa",
+        Lang::Pes => "fa",
+        Lang::Pol => "pl",
+        Lang::Por => "pt",
+        Lang::Ron => "ro",
+        Lang::Rus => "ru",
+        Lang::Sin => "si",
+        Lang::Slk => "sk",
+        Lang::Slv => "sl",
+        Lang::Sna => "sn",
+        Lang::Spa => "es",
+        Lang::Srp => "sr",
+        Lang::Swe => "sv",
+        Lang::Tam => "ta",
+        Lang::Tel => "te",
+        Lang::Tgl => "tl",
+        Lang::Tha => "th",
+        Lang::Tuk => "tk",
+        Lang::Tur => "tr",
+        Lang::Ukr => "uk",
+        Lang::Urd => "ur",
+        Lang::Uzb => "uz",
+        Lang::Vie => "vi",
+        Lang::Yid => "yi",
+        Lang::Zul => "zu",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,6 +500,98 @@ mod tests {
     use tokio::fs::File;
     use tokio::io::AsyncWriteExt;
 
+    #[test]
+    fn test_escape_html_preserve_entities_already_escaped() {
+        let escaped = escape_html("Tom & \"Jerry's\" <show>");
+        assert_eq!(escape_html_preserve_entities(&escaped), escaped);
+        assert_eq!(
+            escape_html_preserve_entities(
+                "&lt;b&gt; &#39; &#x2F; &copy;"
+            ),
+            "&lt;b&gt; &#39; &#x2F; &copy;"
+        );
+        // `escape_html` itself is unchanged and still double escapes.
+        assert_eq!(escape_html("&amp;"), "&amp;amp;");
+    }
+
+    #[test]
+    fn test_escape_html_preserve_entities_mixed() {
+        assert_eq!(
+            escape_html_preserve_entities(
+                "AT&T &amp; R&D <3 &#; &#x; & ;"
+            ),
+            "AT&amp;T &amp; R&amp;D &lt;3 &amp;#; &amp;#x; &amp; ;"
+        );
+        assert_eq!(
+            escape_html_preserve_entities("a&b;c & &#12a; 'q'"),
+            "a&b;c &amp; &amp;#12a; &#x27;q&#x27;"
+        );
+        assert_eq!(escape_html_preserve_entities("&"), "&amp;");
+    }
+
+    #[test]
+    fn test_escape_html_with_text_context() {
+        let input = r#"Tom & "Jerry's" <show>"#;
+        assert_eq!(
+            escape_html_with(input, EscapeFlags::TEXT),
+            r#"Tom &amp; "Jerry's" &lt;show&gt;"#
+        );
+        assert_eq!(
+            escape_html_with(input, EscapeFlags::ALL),
+            escape_html(input)
+        );
+        assert_eq!(
+            escape_html_with(input, EscapeFlags::empty()),
+            input
+        );
+    }
+
+    #[test]
+    fn test_escape_html_with_attribute_contexts() {
+        let input = r#"Say "it's" <here> & now"#;
+        assert_eq!(
+            escape_html_with(
+                input,
+                EscapeFlags::SINGLE_QUOTED_ATTRIBUTE
+            ),
+            r#"Say "it&#x27;s" &lt;here&gt; &amp; now"#
+        );
+        assert_eq!(
+            escape_html_with(
+                input,
+                EscapeFlags::DOUBLE_QUOTED_ATTRIBUTE
+            ),
+            "Say &quot;it's&quot; &lt;here&gt; &amp; now"
+        );
+
+        let mut flags = EscapeFlags::AMPERSAND;
+        flags |= EscapeFlags::DOUBLE_QUOTE;
+        assert_eq!(
+            flags,
+            EscapeFlags::AMPERSAND | EscapeFlags::DOUBLE_QUOTE
+        );
+        assert_eq!(
+            escape_html_with(input, flags),
+            "Say &quot;it's&quot; <here> &amp; now"
+        );
+    }
+
+    #[cfg(feature = "whatlang")]
+    #[test]
+    fn test_detect_language() {
+        let english = "The quick brown fox jumps over the lazy dog \
+            and runs into the forest before night falls.";
+        assert_eq!(detect_language(english).as_deref(), Some("en"));
+
+        let german =
+            "Der schnelle braune Fuchs springt über den faulen \
+            Hund und läuft vor Einbruch der Nacht in den Wald.";
+        assert_eq!(detect_language(german).as_deref(), Some("de"));
+
+        assert_eq!(detect_language(""), None);
+        assert_eq!(detect_language("Bonjour le monde"), None);
+    }
+
     #[test]
     fn test_escape_html() {
         let input = "Hello, <world> & \"friends\"!";
@@ -195,6 +607,25 @@ fn test_escape_html_special_characters() {
         assert_eq!(escape_html(input), expected);
     }
 
+    #[test]
+    fn test_escape_html_attr() {
+        let input = "<a href=\"/path\">`code`</a> & 'q'";
+        let expected = "&lt;a href=&quot;&#x2F;path&quot;&gt;&#x60;code&#x60;&lt;&#x2F;a&gt; &amp; &#x27;q&#x27;";
+        assert_eq!(escape_html_attr(input), expected);
+        assert_eq!(escape_html("/`"), "/`");
+    }
+
+    #[test]
+    fn test_escape_html_attr_roundtrip() {
+        assert_eq!(unescape_html("&#x2F;"), "/");
+
+        let original = "https://example.com/a?b=`c`&d=\"e\"";
+        assert_eq!(
+            unescape_html(&escape_html_attr(original)),
+            original
+        );
+    }
+
     #[test]
     fn test_unescape_html() {
         let input = "Hello, &lt;world&gt; &amp; &quot;friends&quot;!";
@@ -114,3 +114,90 @@ fn test_extract_and_prepare_metadata_invalid_format() {
         }
     }
 }
+
+/// Property tests feeding arbitrary input through the public pipeline.
+///
+/// These only assert that extraction and processing return normally,
+/// whether with `Ok` or `Err`, and never panic.
+mod fuzz {
+    use metadata_gen::{
+        extract_and_prepare_metadata,
+        metadata::{extract_metadata, process_metadata},
+    };
+    use proptest::prelude::*;
+
+    /// Wraps `body` in each supported front matter delimiter.
+    fn front_matter(body: &str) -> Vec<String> {
+        vec![
+            format!("---\n{}\n---\n", body),
+            format!("+++\n{}\n+++\n", body),
+            format!("{{{}}}\n", body),
+            format!(":{}\n\n", body),
+        ]
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(256))]
+
+        #[test]
+        fn random_bytes_never_panic(bytes in prop::collection::vec(any::<u8>(), 0..512)) {
+            let content = String::from_utf8_lossy(&bytes);
+            let _ = extract_metadata(&content);
+            let _ = extract_and_prepare_metadata(&content);
+        }
+
+        #[test]
+        fn random_front_matter_never_panic(body in "\\PC{0,256}") {
+            for content in front_matter(&body) {
+                let _ = extract_metadata(&content);
+                let _ = extract_and_prepare_metadata(&content);
+            }
+        }
+
+        #[test]
+        fn random_fields_never_panic(
+            key in "[a-z.:_-]{1,12}",
+            value in "[\\PC\"'\\[\\]{}/:,#&<> -]{0,48}",
+            date in "[0-9/: TZ+-]{0,24}",
+        ) {
+            let yaml = format!(
+                "title: {v}\ndate: {d}\n{k}: {v}\n",
+                k = key,
+                v = value,
+                d = date
+            );
+            let toml = format!(
+                "title = \"{v}\"\ndate = \"{d}\"\n",
+                v = value.replace(['"', '\\'], ""),
+                d = date
+            );
+            let json = format!(
+                "\"title\": \"{v}\", \"date\": \"{d}\"",
+                v = value.replace(['"', '\\'], ""),
+                d = date
+            );
+            for content in [
+                format!("---\n{}---\n{}", yaml, value),
+                format!("+++\n{}+++\n", toml),
+                format!("{{{}}}\n", json),
+            ] {
+                let _ = extract_and_prepare_metadata(&content);
+            }
+        }
+
+        #[test]
+        fn random_extracted_metadata_processes_without_panic(
+            title in "\\PC{0,48}",
+            date in "[0-9a-zA-Z/:., TZ+-]{0,32}",
+            extra in "[a-z_]{1,8}: \\PC{0,32}",
+        ) {
+            let content = format!(
+                "---\ntitle: {:?}\ndate: {:?}\n{}\n---\nBody",
+                title, date, extra
+            );
+            if let Ok(metadata) = extract_metadata(&content) {
+                let _ = process_metadata(&metadata);
+            }
+        }
+    }
+}
@@ -68,7 +68,7 @@ fn test_add_custom_og_tag() {
         meta_tags.add_custom_tag("og:custom", "custom og value");
 
         assert!(meta_tags.og.contains(
-            "<meta name=\"og:custom\" content=\"custom og value\">"
+            "<meta property=\"og:custom\" content=\"custom og value\">"
         ));
     }
 
@@ -124,7 +124,7 @@ fn test_add_multiple_custom_tags() {
         // Check Open Graph (og) meta tags
         assert!(
             meta_tags.og.contains(
-                "<meta name=\"og:custom\" content=\"og value\">"
+                "<meta property=\"og:custom\" content=\"og value\">"
             ),
             "OG meta tag should contain 'og:custom'"
         );